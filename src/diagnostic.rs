@@ -0,0 +1,329 @@
+use crate::expression;
+use crate::interpreter::RuntimeError;
+use crate::lexer;
+use crate::resolver;
+use crate::span::Span;
+
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// An error message, usually tied to the stretch of source it is about, ready to be shown to
+/// the user either as text or as JSON.
+#[derive(Debug)]
+pub struct Diagnostic {
+    code: &'static str,
+    message: String,
+    span: Option<Span>,
+    help: Option<String>,
+    expected: Vec<String>,
+    found: Option<String>,
+    trace: Vec<(String, Span)>,
+}
+
+impl Diagnostic {
+    pub fn new(code: &'static str, message: String, span: Span) -> Self {
+        Self {
+            code,
+            message,
+            span: Some(span),
+            help: None,
+            expected: Vec::new(),
+            found: None,
+            trace: Vec::new(),
+        }
+    }
+
+    /// A diagnostic about the file as a whole, such as failing to read it.
+    pub fn unlocated(code: &'static str, message: String) -> Self {
+        Self {
+            code,
+            message,
+            span: None,
+            help: None,
+            expected: Vec::new(),
+            found: None,
+            trace: Vec::new(),
+        }
+    }
+
+    pub fn with_help(mut self, help: Option<String>) -> Self {
+        self.help = help;
+        self
+    }
+
+    /// Records what the parser would have accepted and what it found instead. These only show up
+    /// in JSON, as the message already says as much.
+    pub fn with_expected(mut self, expected: Vec<String>, found: Option<String>) -> Self {
+        self.expected = expected;
+        self.found = found;
+        self
+    }
+
+    /// Adds a stack trace, given as what was running (`fib()`, `script`) and where, innermost
+    /// first.
+    pub fn with_trace(mut self, trace: Vec<(String, Span)>) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// Renders the message followed by the offending source line with the span underlined:
+    ///
+    /// ```text
+    /// error[E0233]: Expect ';' after value, found end of file.
+    ///  --> script.lox:1:8
+    ///   |
+    /// 1 | print 1
+    ///   |        ^
+    /// ```
+    ///
+    /// followed by a `= help: ...` line if there is a hint, and by the stack trace, if there is
+    /// one, one `[script.lox:3] in fib()` line per frame. A run of identical frames, as left by
+    /// runaway recursion, is shown once with a count.
+    ///
+    /// `colour` adds ANSI escapes and should only be set when writing to a terminal.
+    pub fn render(&self, file: &str, source: &str, colour: bool) -> String {
+        let paint = |style: &'static str| if colour { style } else { "" };
+        let (red, blue, bold, reset) = (paint(RED), paint(BLUE), paint(BOLD), paint(RESET));
+        let header = format!("{red}error[{}]{reset}{bold}: {}{reset}\n", self.code, self.message);
+        let Some(span) = self.span else {
+            return format!("{header}{blue}-->{reset} {file}\n");
+        };
+
+        let line = source
+            .lines()
+            .nth(span.line.saturating_sub(1) as usize)
+            .unwrap_or("");
+        let before: String = line
+            .chars()
+            .take(span.column.saturating_sub(1) as usize)
+            .map(|x| if x == '\t' { '\t' } else { ' ' })
+            .collect();
+        // Spans running past the end of the line are cut off there.
+        let width = source
+            .get(span.start..span.end)
+            .map_or(0, |text| text.lines().next().unwrap_or("").chars().count())
+            .min(line.chars().count().saturating_sub(before.chars().count()))
+            .max(1);
+
+        let number = span.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let help = match &self.help {
+            Some(help) => format!("{gutter} {blue}={reset} {bold}help{reset}: {help}\n"),
+            None => String::new(),
+        };
+        let mut trace = String::new();
+        let mut frames = self.trace.iter().peekable();
+        while let Some((frame, span)) = frames.next() {
+            trace += &format!("{blue}[{file}:{}]{reset} in {frame}\n", span.line);
+            let mut repeated = 0;
+            while frames.next_if(|(x, y)| x == frame && y.line == span.line).is_some() {
+                repeated += 1;
+            }
+            if repeated > 0 {
+                trace += &format!("{blue}[previous line repeated {repeated} more times]{reset}\n");
+            }
+        }
+        format!(
+            "{header}\
+             {gutter}{blue}-->{reset} {file}:{}:{}\n\
+             {gutter} {blue}|{reset}\n\
+             {blue}{number} |{reset} {line}\n\
+             {gutter} {blue}|{reset} {before}{red}{}{reset}\n\
+             {help}{trace}",
+            span.line,
+            span.column,
+            "^".repeat(width),
+        )
+    }
+
+    /// Renders the diagnostic as a single line of JSON, without a trailing newline:
+    ///
+    /// ```text
+    /// {"severity":"error","code":"E0233","message":"Expect ';' after value, found end of file.","file":"script.lox",
+    ///  "span":{"start":7,"end":7,"line":1,"column":8},"help":null,"expected":["';'"],
+    ///  "found":"end of file","trace":[]}
+    /// ```
+    ///
+    /// `span` is `null` for diagnostics that aren't tied to the source, `help` when there is no
+    /// hint and `found` when the error isn't about an unexpected token. Each `trace` entry has
+    /// a `frame` plus the `line` and `column` it was at.
+    pub fn to_json(&self, file: &str) -> String {
+        let span = match self.span {
+            Some(span) => format!(
+                r#"{{"start":{},"end":{},"line":{},"column":{}}}"#,
+                span.start, span.end, span.line, span.column
+            ),
+            None => "null".to_string(),
+        };
+        let help = self.help.as_deref().map_or("null".to_string(), json_string);
+        let expected: Vec<String> = self.expected.iter().map(|x| json_string(x)).collect();
+        let found = self.found.as_deref().map_or("null".to_string(), json_string);
+        let trace: Vec<String> = self
+            .trace
+            .iter()
+            .map(|(frame, span)| {
+                format!(r#"{{"frame":{},"line":{},"column":{}}}"#, json_string(frame), span.line, span.column)
+            })
+            .collect();
+        format!(
+            r#"{{"severity":"error","code":{},"message":{},"file":{},"span":{span},"help":{help},"expected":[{}],"found":{found},"trace":[{}]}}"#,
+            json_string(self.code),
+            json_string(&self.message),
+            json_string(file),
+            expected.join(","),
+            trace.join(","),
+        )
+    }
+}
+
+/// Quotes `text` as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for x in text.chars() {
+        match x {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            x if x.is_control() => quoted.push_str(&format!("\\u{:04x}", x as u32)),
+            x => quoted.push(x),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl From<&lexer::Error> for Diagnostic {
+    fn from(err: &lexer::Error) -> Self {
+        Self::new(err.kind.code(), err.kind.to_string(), err.span)
+    }
+}
+
+impl From<&expression::Error> for Diagnostic {
+    fn from(err: &expression::Error) -> Self {
+        let expected = err.expected().iter().map(ToString::to_string).collect();
+        Self::new(err.kind.code(), err.message(), err.span)
+            .with_help(err.help.clone())
+            .with_expected(expected, err.found.as_ref().map(ToString::to_string))
+    }
+}
+
+impl From<&resolver::Error> for Diagnostic {
+    fn from(err: &resolver::Error) -> Self {
+        Self::new(err.code(), err.message().to_string(), err.token().span)
+    }
+}
+
+impl From<&RuntimeError> for Diagnostic {
+    fn from(err: &RuntimeError) -> Self {
+        let diagnostic =
+            Self::new(err.kind.code(), err.kind.to_string(), err.span).with_help(err.help());
+        // An error raised at the top level has no calls worth listing.
+        if err.trace.is_empty() {
+            return diagnostic;
+        }
+        let trace = err
+            .stack()
+            .map(|(function, span)| match function {
+                Some(function) => (format!("{function}()"), span),
+                None => ("script".to_string(), span),
+            })
+            .collect();
+        diagnostic.with_trace(trace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Diagnostic;
+    use crate::span::Span;
+
+    #[test]
+    fn test_diagnostic_render() {
+        let source = "var a = 1;\nprint a + nil;\n";
+        let diagnostic = Diagnostic::new("E0000", "Operand must be a number.".to_string(), Span::new(21, 24, 2, 11));
+        let expect = "\
+error[E0000]: Operand must be a number.
+ --> test.lox:2:11
+  |
+2 | print a + nil;
+  |           ^^^
+";
+        assert_eq!(diagnostic.render("test.lox", source, false), expect);
+    }
+
+    #[test]
+    fn test_diagnostic_render_end_of_input() {
+        let diagnostic = Diagnostic::new("E0000", "Expect expression.".to_string(), Span::new(4, 4, 2, 1));
+        let rendered = diagnostic.render("test.lox", "1 +\n", false);
+        assert!(rendered.ends_with("2 | \n  | ^\n"), "{rendered}");
+    }
+
+    #[test]
+    fn test_diagnostic_render_keeps_tabs_and_cuts_multiline_spans() {
+        let source = "\tprint \"a\nb\";";
+        let diagnostic = Diagnostic::new("E0000", "Unterminated.".to_string(), Span::new(7, 12, 1, 8));
+        let rendered = diagnostic.render("test.lox", source, false);
+        assert!(rendered.ends_with("1 | \tprint \"a\n  | \t      ^^\n"), "{rendered}");
+    }
+
+    #[test]
+    fn test_diagnostic_render_trace() {
+        let source = "fun f() {\n  return -nil;\n}\nf();";
+        let diagnostic = Diagnostic::new("E0000", "Oops.".to_string(), Span::new(20, 23, 2, 11)).with_trace(vec![
+            ("f()".to_string(), Span::new(20, 23, 2, 11)),
+            ("script".to_string(), Span::new(27, 30, 4, 1)),
+        ]);
+        let rendered = diagnostic.render("test.lox", source, false);
+        assert!(rendered.ends_with("^^^\n[test.lox:2] in f()\n[test.lox:4] in script\n"), "{rendered}");
+
+        let frame = ("f()".to_string(), Span::new(20, 23, 2, 11));
+        let diagnostic = Diagnostic::new("E0000", "Oops.".to_string(), Span::new(20, 23, 2, 11))
+            .with_trace(vec![frame.clone(), frame.clone(), frame, ("script".to_string(), Span::new(27, 30, 4, 1))]);
+        let rendered = diagnostic.render("test.lox", source, false);
+        let expect = "^^^\n[test.lox:2] in f()\n[previous line repeated 2 more times]\n[test.lox:4] in script\n";
+        assert!(rendered.ends_with(expect), "{rendered}");
+    }
+
+    #[test]
+    fn test_diagnostic_help() {
+        let diagnostic = Diagnostic::new("E0000", "Oops.".to_string(), Span::new(0, 5, 1, 1))
+            .with_help(Some("did you mean 'print'?".to_string()));
+        let rendered = diagnostic.render("test.lox", "pritn", false);
+        assert!(rendered.ends_with("^^^^^\n  = help: did you mean 'print'?\n"), "{rendered}");
+        assert!(diagnostic.to_json("x").contains(r#""help":"did you mean 'print'?""#));
+    }
+
+    #[test]
+    fn test_diagnostic_render_colour() {
+        let diagnostic = Diagnostic::new("E0000", "Oops.".to_string(), Span::new(0, 1, 1, 1));
+        assert!(diagnostic.render("test.lox", "x", true).contains("\x1b[1;31m^\x1b[0m"));
+        assert!(!diagnostic.render("test.lox", "x", false).contains('\x1b'));
+    }
+
+    #[test]
+    fn test_diagnostic_render_unlocated() {
+        let diagnostic = Diagnostic::unlocated("E0000", "Could not read it.".to_string());
+        assert_eq!(diagnostic.render("test.lox", "", false), "error[E0000]: Could not read it.\n--> test.lox\n");
+    }
+
+    #[test]
+    fn test_diagnostic_to_json() {
+        let diagnostic = Diagnostic::new("E0000", "Expect ';' after \"x\".".to_string(), Span::new(7, 8, 1, 8))
+            .with_trace(vec![("f()".to_string(), Span::new(7, 8, 1, 8))]);
+        let expect = r#"{"severity":"error","code":"E0000","message":"Expect ';' after \"x\".","file":"dir\\test.lox","span":{"start":7,"end":8,"line":1,"column":8},"help":null,"expected":[],"found":null,"trace":[{"frame":"f()","line":1,"column":8}]}"#;
+        assert_eq!(diagnostic.to_json("dir\\test.lox"), expect);
+
+        let diagnostic = Diagnostic::unlocated("E0000", "Tab\there.\u{1}".to_string());
+        let expect = r#"{"severity":"error","code":"E0000","message":"Tab\there.\u0001","file":"x","span":null,"help":null,"expected":[],"found":null,"trace":[]}"#;
+        assert_eq!(diagnostic.to_json("x"), expect);
+    }
+}
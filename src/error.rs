@@ -0,0 +1,159 @@
+use crate::diagnostic::Diagnostic;
+use crate::expression;
+use crate::interpreter::RuntimeError;
+use crate::lexer;
+use crate::resolver;
+
+/// The command was used incorrectly.
+pub const EX_USAGE: u8 = 64;
+/// The input script was malformed.
+pub const EX_DATAERR: u8 = 65;
+/// The script failed while running.
+pub const EX_SOFTWARE: u8 = 70;
+/// The script couldn't be read.
+pub const EX_IOERR: u8 = 74;
+
+/// Anything that can stop a script, from reading its file through to running it.
+#[derive(Debug)]
+pub enum Error {
+    Usage,
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+    Lex(lexer::Error),
+    Parse(expression::Error),
+    Resolve(resolver::Error),
+    Runtime(RuntimeError),
+}
+
+impl Error {
+    /// The sysexits(3) code the process should exit with when this error stops it.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Usage => EX_USAGE,
+            Error::Io { .. } => EX_IOERR,
+            Error::Lex(_) | Error::Parse(_) | Error::Resolve(_) => EX_DATAERR,
+            Error::Runtime(_) => EX_SOFTWARE,
+        }
+    }
+
+    /// The error as a diagnostic, pointing into the source when it came from the source.
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            Error::Usage => Diagnostic::unlocated("E0501", self.to_string()),
+            Error::Io { .. } => Diagnostic::unlocated("E0502", self.to_string()),
+            Error::Lex(err) => err.into(),
+            Error::Parse(err) => err.into(),
+            Error::Resolve(err) => err.into(),
+            Error::Runtime(err) => err.into(),
+        }
+    }
+}
+
+/// The lexical and syntax errors in a script, in the order they occur. A syntax error about
+/// the token right after an invalid one is left out, since the parser only stumbled over the
+/// gap that the lexical error already accounts for.
+pub fn syntax_errors<C: Iterator<Item = char>>(
+    lexer: &mut lexer::Lexer<C>,
+    parse_errors: Vec<expression::Error>,
+) -> Vec<Error> {
+    let parse_errors: Vec<Error> = parse_errors
+        .into_iter()
+        .filter(|err| !err.found.as_ref().is_some_and(|found| lexer.follows_error(found)))
+        .map(Into::into)
+        .collect();
+    let mut errors: Vec<Error> = lexer.take_errors().into_iter().map(Into::into).collect();
+    errors.extend(parse_errors);
+    errors.sort_by_key(|x| x.diagnostic().span().map_or(0, |x| x.start));
+    errors
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage => write!(f, "Usage: jrox [--error-format=human|json] [--string-coercion] [script]"),
+            Error::Io { path, source } => write!(f, "Could not read '{path}': {source}."),
+            Error::Lex(err) => write!(
+                f,
+                "[line {}:{}] Error: {}",
+                err.span.line, err.span.column, err.kind
+            ),
+            Error::Parse(err) => write!(f, "{err}"),
+            Error::Resolve(err) => write!(f, "{err}"),
+            Error::Runtime(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<lexer::Error> for Error {
+    fn from(err: lexer::Error) -> Self {
+        Error::Lex(err)
+    }
+}
+
+impl From<expression::Error> for Error {
+    fn from(err: expression::Error) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<resolver::Error> for Error {
+    fn from(err: resolver::Error) -> Self {
+        Error::Resolve(err)
+    }
+}
+
+impl From<RuntimeError> for Error {
+    fn from(err: RuntimeError) -> Self {
+        Error::Runtime(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{syntax_errors, Error, EX_DATAERR, EX_IOERR, EX_SOFTWARE};
+    use crate::expression::ErrorKind;
+    use crate::interpreter::RuntimeErrorKind;
+    use crate::lexer::Lexer;
+    use crate::parser;
+    use crate::span::Span;
+    use crate::token;
+
+    #[test]
+    fn test_error_exit_codes() {
+        let parse: Error = ErrorKind::ExpectExpression.at(Span::default()).into();
+        let runtime: Error = RuntimeErrorKind::UndefinedVariable { name: "a".to_string(), suggestion: None }
+            .at(Span::default())
+            .into();
+        let io = Error::Io {
+            path: "missing.lox".to_string(),
+            source: std::io::ErrorKind::NotFound.into(),
+        };
+        assert_eq!(parse.exit_code(), EX_DATAERR);
+        assert_eq!(runtime.exit_code(), EX_SOFTWARE);
+        assert_eq!(io.exit_code(), EX_IOERR);
+        assert!(io.diagnostic().span().is_none());
+    }
+
+    #[test]
+    fn test_error_syntax_errors_skip_what_follows_an_invalid_token() {
+        let errors = |source: &str| {
+            let mut lexer = Lexer::from_iter(source.chars());
+            let parsed = parser::parse(&mut lexer.by_ref().peekable());
+            syntax_errors(&mut lexer, parsed.err().unwrap_or_default())
+        };
+        for (source, kind) in [
+            ("print \"abc", token::Error::UnterminatedString),
+            ("print 1 + @;", token::Error::UnexpectedCharacter),
+        ] {
+            let errors = errors(source);
+            assert_eq!(errors.len(), 1, "{source}: {errors:?}");
+            assert!(matches!(&errors[0], Error::Lex(err) if err.kind == kind), "{source}: {errors:?}");
+        }
+
+        let errors = errors("print @ 1;\nprint ;");
+        assert_eq!(errors.len(), 2, "Errors further on are still reported: {errors:?}");
+        assert!(matches!(&errors[1], Error::Parse(err) if matches!(err.kind, ErrorKind::ExpectExpression)));
+    }
+}
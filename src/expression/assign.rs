@@ -0,0 +1,50 @@
+use std::cell::Cell;
+
+use super::Expr;
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AssignExpression {
+    name: Token,
+    value: Box<Expr>,
+    depth: Cell<Option<usize>>,
+}
+
+impl std::fmt::Display for AssignExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.name.lexeme, self.value)
+    }
+}
+
+impl AssignExpression {
+    pub fn new(name: Token, value: Expr) -> Self {
+        Self {
+            name,
+            value: Box::new(value),
+            depth: Cell::new(None),
+        }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Expr {
+        self.value.as_ref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.name.span.to(self.value.span())
+    }
+
+    /// How many scopes out from the assignment the variable was declared, or `None` for a
+    /// global. Filled in by the resolver.
+    pub fn depth(&self) -> Option<usize> {
+        self.depth.get()
+    }
+
+    pub fn resolve(&self, depth: usize) {
+        self.depth.set(Some(depth));
+    }
+}
@@ -1,4 +1,5 @@
 use super::Expr;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct BinaryExpression {
@@ -34,6 +35,14 @@ impl BinaryExpression {
         Self::new(left, BinaryOperator::Div, right)
     }
 
+    pub fn int_div(left: Expr, right: Expr) -> Self {
+        Self::new(left, BinaryOperator::IntDiv, right)
+    }
+
+    pub fn modulo(left: Expr, right: Expr) -> Self {
+        Self::new(left, BinaryOperator::Mod, right)
+    }
+
     pub fn sub(left: Expr, right: Expr) -> Self {
         Self::new(left, BinaryOperator::Sub, right)
     }
@@ -73,6 +82,10 @@ impl BinaryExpression {
     pub fn right(&self) -> &Expr {
         self.right.as_ref()
     }
+
+    pub fn span(&self) -> Span {
+        self.left.span().to(self.right.span())
+    }
 }
 
 
@@ -80,6 +93,9 @@ impl BinaryExpression {
 pub enum BinaryOperator {
     Mult,
     Div,
+    /// Division that drops the fractional part of the result, written `~/`.
+    IntDiv,
+    Mod,
     Add,
     Sub,
     Greater,
@@ -97,6 +113,8 @@ impl std::fmt::Display for BinaryOperator {
             Self::Sub => write!(f, "-"),
             Self::Mult => write!(f, "*"),
             Self::Div => write!(f, "/"),
+            Self::IntDiv => write!(f, "~/"),
+            Self::Mod => write!(f, "%"),
             Self::Greater => write!(f, ">"),
             Self::Less => write!(f, "<"),
             Self::GreaterEqual => write!(f, ">="),
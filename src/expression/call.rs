@@ -0,0 +1,46 @@
+use super::Expr;
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CallExpression {
+    callee: Box<Expr>,
+    paren: Token,
+    arguments: Vec<Expr>,
+}
+
+impl std::fmt::Display for CallExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.callee)?;
+        for (i, argument) in self.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{argument}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl CallExpression {
+    /// `paren` is the closing parenthesis of the argument list, kept to locate the call.
+    pub fn new(callee: Expr, paren: Token, arguments: Vec<Expr>) -> Self {
+        Self {
+            callee: Box::new(callee),
+            paren,
+            arguments,
+        }
+    }
+
+    pub fn callee(&self) -> &Expr {
+        self.callee.as_ref()
+    }
+
+    pub fn arguments(&self) -> &[Expr] {
+        &self.arguments
+    }
+
+    pub fn span(&self) -> Span {
+        self.callee.span().to(self.paren.span)
+    }
+}
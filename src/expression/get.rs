@@ -0,0 +1,36 @@
+use super::Expr;
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GetExpression {
+    object: Box<Expr>,
+    name: Token,
+}
+
+impl std::fmt::Display for GetExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.object, self.name.lexeme)
+    }
+}
+
+impl GetExpression {
+    pub fn new(object: Expr, name: Token) -> Self {
+        Self {
+            object: Box::new(object),
+            name,
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        self.object.as_ref()
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn span(&self) -> Span {
+        self.object.span().to(self.name.span)
+    }
+}
@@ -0,0 +1,38 @@
+use super::Expr;
+use crate::span::Span;
+
+/// A parenthesized expression, kept so that the parentheses can be located.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GroupingExpression {
+    expression: Box<Expr>,
+    span: Span,
+}
+
+impl std::fmt::Display for GroupingExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl GroupingExpression {
+    pub fn new(expression: Expr) -> Self {
+        Self {
+            span: expression.span(),
+            expression: Box::new(expression),
+        }
+    }
+
+    pub fn expression(&self) -> &Expr {
+        self.expression.as_ref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Widens the span to take in the parentheses.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+}
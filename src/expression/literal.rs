@@ -1,10 +1,13 @@
+use crate::span::Span;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct LiteralExpression {
     value: LiteralOperator,
+    span: Span,
 }
 
 impl LiteralExpression {
-    pub fn new(operator: LiteralOperator) -> Self { Self { value: operator } }
+    pub fn new(operator: LiteralOperator) -> Self { Self { value: operator, span: Span::default() } }
 
     pub fn boolean(value: bool) -> Self {
         Self::new(LiteralOperator::Boolean(value))
@@ -18,6 +21,15 @@ impl LiteralExpression {
         Self::new(LiteralOperator::Number(value))
     }
 
+    pub fn integer(value: i64) -> Self {
+        Self::new(LiteralOperator::Integer(value))
+    }
+
+    #[cfg(feature = "bigint")]
+    pub fn big_integer(value: num_bigint::BigInt) -> Self {
+        Self::new(LiteralOperator::BigInteger(value))
+    }
+
     pub fn string(value: String) -> Self {
         Self::new(LiteralOperator::String(value))
     }
@@ -25,6 +37,16 @@ impl LiteralExpression {
     pub fn value(&self) -> &LiteralOperator {
         &self.value
     }
+
+    /// Where the literal was written.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
 }
 
 impl std::fmt::Display for LiteralExpression {
@@ -38,24 +60,20 @@ pub enum LiteralOperator {
     Boolean(bool),
     Nil,
     Number(f64),
+    Integer(i64),
+    #[cfg(feature = "bigint")]
+    BigInteger(num_bigint::BigInt),
     String(String),
 }
 
-impl LiteralOperator {
-    pub fn as_number(&self) -> Option<&f64> {
-        if let Self::Number(v) = self {
-            Some(v)
-        } else {
-            None
-        }
-    }
-}
-
 impl std::fmt::Display for LiteralOperator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Boolean(x) => write!(f, "{x}"),
             Self::Number(x) => write!(f, "{x}"),
+            Self::Integer(x) => write!(f, "{x}"),
+            #[cfg(feature = "bigint")]
+            Self::BigInteger(x) => write!(f, "{x}"),
             Self::String(x) => write!(f, "{x}"),
             Self::Nil => write!(f, "nil"),
         }
@@ -0,0 +1,64 @@
+use super::Expr;
+use crate::span::Span;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogicalExpression {
+    left: Box<Expr>,
+    operator: LogicalOperator,
+    right: Box<Expr>,
+}
+
+impl std::fmt::Display for LogicalExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.left, self.operator, self.right)
+    }
+}
+
+impl LogicalExpression {
+    pub fn new(left: Expr, operator: LogicalOperator, right: Expr) -> Self {
+        Self {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }
+    }
+
+    pub fn and(left: Expr, right: Expr) -> Self {
+        Self::new(left, LogicalOperator::And, right)
+    }
+
+    pub fn or(left: Expr, right: Expr) -> Self {
+        Self::new(left, LogicalOperator::Or, right)
+    }
+
+    pub fn left(&self) -> &Expr {
+        self.left.as_ref()
+    }
+
+    pub fn operator(&self) -> LogicalOperator {
+        self.operator
+    }
+
+    pub fn right(&self) -> &Expr {
+        self.right.as_ref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.left.span().to(self.right.span())
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LogicalOperator {
+    And,
+    Or,
+}
+
+impl std::fmt::Display for LogicalOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::And => write!(f, "and"),
+            Self::Or => write!(f, "or"),
+        }
+    }
+}
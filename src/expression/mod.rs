@@ -1,28 +1,68 @@
 
+mod assign;
 mod binary;
+mod call;
+mod get;
+mod grouping;
 mod literal;
+mod logical;
+mod set;
+mod super_method;
+mod this;
 mod unary;
+mod variable;
 
 pub use self::literal::{LiteralExpression, LiteralOperator};
+pub use self::logical::{LogicalExpression, LogicalOperator};
 pub use self::unary::{UnaryExpression, UnaryOperator};
 pub use self::binary::{BinaryExpression, BinaryOperator};
+pub use self::assign::AssignExpression;
+pub use self::call::CallExpression;
+pub use self::get::GetExpression;
+pub use self::grouping::GroupingExpression;
+pub use self::set::SetExpression;
+pub use self::super_method::SuperExpression;
+pub use self::this::ThisExpression;
+pub use self::variable::VariableExpression;
+
+use crate::span::Span;
+use crate::token::{Keyword, Token, TokenKind};
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum Expr {
     Arithmetic(binary::BinaryExpression),
+    Assign(assign::AssignExpression),
+    Call(call::CallExpression),
     Compare(binary::BinaryExpression),
     Equality(binary::BinaryExpression),
-    Grouping(Box<Expr>),
+    Get(get::GetExpression),
+    Grouping(grouping::GroupingExpression),
     Literal(literal::LiteralExpression),
+    Logical(logical::LogicalExpression),
+    Set(set::SetExpression),
+    Super(super_method::SuperExpression),
+    This(this::ThisExpression),
     Unary(unary::UnaryExpression),
+    Variable(variable::VariableExpression),
 }
 
 impl Expr {
-    pub fn as_literal(&self) -> Option<&literal::LiteralExpression> {
-        if let Self::Literal(v) = self {
-            Some(v)
-        } else {
-            None
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Arithmetic(e) => e.span(),
+            Self::Assign(e) => e.span(),
+            Self::Call(e) => e.span(),
+            Self::Compare(e) => e.span(),
+            Self::Equality(e) => e.span(),
+            Self::Get(e) => e.span(),
+            Self::Grouping(e) => e.span(),
+            Self::Literal(e) => e.span(),
+            Self::Logical(e) => e.span(),
+            Self::Set(e) => e.span(),
+            Self::Super(e) => e.span(),
+            Self::This(e) => e.span(),
+            Self::Unary(e) => e.span(),
+            Self::Variable(e) => e.span(),
         }
     }
 }
@@ -32,17 +72,256 @@ impl std::fmt::Display for Expr {
         match self {
             Self::Unary(expr) => write!(f, "({expr})"),
             Self::Arithmetic(expr) => write!(f, "({expr})"),
+            Self::Assign(expr) => write!(f, "({expr})"),
+            Self::Call(expr) => write!(f, "{expr}"),
             Self::Compare(expr) => write!(f, "({expr})"),
             Self::Equality(expr) => write!(f, "({expr})"),
+            Self::Get(expr) => write!(f, "{expr}"),
             Self::Grouping(e) => write!(f, "({e})"),
             Self::Literal(x) => write!(f, "{x}"),
+            Self::Logical(expr) => write!(f, "({expr})"),
+            Self::Set(expr) => write!(f, "({expr})"),
+            Self::Super(expr) => write!(f, "{expr}"),
+            Self::This(expr) => write!(f, "{expr}"),
+            Self::Variable(x) => write!(f, "{x}"),
         }
     }
 }
 
+/// The most arguments a call, and the most parameters a function, may have.
+pub const MAX_ARGUMENTS: usize = 255;
+
+/// A parse error: what went wrong and where. Errors about a token the parser didn't expect
+/// carry that token, so that they can say what was found instead.
 #[derive(Debug)]
-pub enum Error {
+pub struct Error {
+    pub kind: ErrorKind,
+    pub span: Span,
+    pub found: Option<Box<Token>>,
+    /// A hint at how to fix the error, such as the keyword a misspelled identifier resembles.
+    pub help: Option<String>,
+}
+
+impl Error {
+    /// The kinds of token the parser would have accepted where it failed.
+    pub fn expected(&self) -> Vec<TokenKind> {
+        self.kind.expected()
+    }
+
+    /// The error message, e.g. `Expect ';' after value, found 'print'.`
+    pub fn message(&self) -> String {
+        match &self.found {
+            Some(found) => format!("{}, found {found}.", self.kind),
+            None => format!("{}.", self.kind),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[line {}:{}] Error: {}", self.span.line, self.span.column, self.message())
+    }
+}
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    ExpectClassName,
+    ExpectDotAfterSuper,
     ExpectExpression,
-    ExpectRightParen,
-    UnexpecedCharacter(crate::token::TokenKind),
+    ExpectFunctionName,
+    ExpectLeftBrace,
+    ExpectLeftParenAfterFunctionName,
+    ExpectLeftParenAfterIf,
+    ExpectLeftParenAfterWhile,
+    ExpectLeftParenAfterFor,
+    ExpectLoopAfterLabel,
+    ExpectParameterName,
+    ExpectPropertyName,
+    ExpectRightBrace,
+    ExpectRightParenAfterExpression,
+    ExpectRightParenAfterParameters,
+    ExpectRightParenAfterArguments,
+    ExpectRightParenAfterCondition,
+    ExpectRightParenAfterForClauses,
+    ExpectSemicolonAfterExpression,
+    ExpectSemicolonAfterValue,
+    ExpectSemicolonAfterVariable,
+    ExpectSemicolonAfterReturnValue,
+    ExpectSemicolonAfterBreak,
+    ExpectSemicolonAfterContinue,
+    ExpectSemicolonAfterLoopCondition,
+    ExpectSuperclassMethodName,
+    ExpectSuperclassName,
+    ExpectVariableName,
+    InvalidAssignmentTarget,
+    TooManyArguments,
+    TooManyParameters,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+    UndefinedLabel(String),
+    /// Never raised with the `bigint` feature, which accepts integer literals of any length.
+    #[cfg_attr(feature = "bigint", allow(dead_code))]
+    IntegerTooLarge,
+}
+
+impl ErrorKind {
+    pub fn at(self, span: Span) -> Error {
+        Error {
+            kind: self,
+            span,
+            found: None,
+            help: None,
+        }
+    }
+
+    /// An error located at `token`, which is what the parser found instead of what it expected.
+    pub fn found(self, token: Token) -> Error {
+        Error {
+            kind: self,
+            span: token.span,
+            found: Some(Box::new(token)),
+            help: None,
+        }
+    }
+
+    /// A stable identifier for the kind of error, for tools that consume diagnostics. Codes of
+    /// removed kinds are never reused.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ExpectClassName => "E0201",
+            Self::ExpectDotAfterSuper => "E0202",
+            Self::ExpectExpression => "E0203",
+            Self::ExpectFunctionName => "E0204",
+            Self::ExpectLeftBrace => "E0205",
+            Self::ExpectLoopAfterLabel => "E0207",
+            Self::ExpectParameterName => "E0208",
+            Self::ExpectPropertyName => "E0209",
+            Self::ExpectRightBrace => "E0210",
+            Self::ExpectRightParenAfterExpression => "E0211",
+            Self::ExpectSuperclassMethodName => "E0213",
+            Self::ExpectSuperclassName => "E0214",
+            Self::ExpectVariableName => "E0215",
+            Self::InvalidAssignmentTarget => "E0216",
+            Self::TooManyArguments => "E0217",
+            Self::TooManyParameters => "E0218",
+            Self::BreakOutsideLoop => "E0219",
+            Self::ContinueOutsideLoop => "E0220",
+            Self::UndefinedLabel(_) => "E0221",
+            Self::IntegerTooLarge => "E0223",
+            Self::ExpectLeftParenAfterFunctionName => "E0224",
+            Self::ExpectLeftParenAfterIf => "E0225",
+            Self::ExpectLeftParenAfterWhile => "E0226",
+            Self::ExpectLeftParenAfterFor => "E0227",
+            Self::ExpectRightParenAfterParameters => "E0228",
+            Self::ExpectRightParenAfterArguments => "E0229",
+            Self::ExpectRightParenAfterCondition => "E0230",
+            Self::ExpectRightParenAfterForClauses => "E0231",
+            Self::ExpectSemicolonAfterExpression => "E0232",
+            Self::ExpectSemicolonAfterValue => "E0233",
+            Self::ExpectSemicolonAfterVariable => "E0234",
+            Self::ExpectSemicolonAfterReturnValue => "E0235",
+            Self::ExpectSemicolonAfterBreak => "E0236",
+            Self::ExpectSemicolonAfterContinue => "E0237",
+            Self::ExpectSemicolonAfterLoopCondition => "E0238",
+        }
+    }
+
+    /// The kinds of token that would have avoided the error, if it is about a missing token.
+    pub fn expected(&self) -> Vec<TokenKind> {
+        match self {
+            Self::ExpectClassName
+            | Self::ExpectFunctionName
+            | Self::ExpectParameterName
+            | Self::ExpectPropertyName
+            | Self::ExpectSuperclassMethodName
+            | Self::ExpectSuperclassName
+            | Self::ExpectVariableName => vec![TokenKind::Identifiter],
+            Self::ExpectDotAfterSuper => vec![TokenKind::Dot],
+            Self::ExpectLeftBrace => vec![TokenKind::LeftBrace],
+            Self::ExpectLeftParenAfterFunctionName
+            | Self::ExpectLeftParenAfterIf
+            | Self::ExpectLeftParenAfterWhile
+            | Self::ExpectLeftParenAfterFor => vec![TokenKind::LeftParen],
+            Self::ExpectRightBrace => vec![TokenKind::RightBrace],
+            Self::ExpectRightParenAfterExpression
+            | Self::ExpectRightParenAfterParameters
+            | Self::ExpectRightParenAfterArguments
+            | Self::ExpectRightParenAfterCondition
+            | Self::ExpectRightParenAfterForClauses => vec![TokenKind::RightParen],
+            Self::ExpectSemicolonAfterExpression
+            | Self::ExpectSemicolonAfterValue
+            | Self::ExpectSemicolonAfterVariable
+            | Self::ExpectSemicolonAfterReturnValue
+            | Self::ExpectSemicolonAfterBreak
+            | Self::ExpectSemicolonAfterContinue
+            | Self::ExpectSemicolonAfterLoopCondition => vec![TokenKind::Semicolon],
+            Self::ExpectLoopAfterLabel => {
+                vec![TokenKind::Keyword(Keyword::While), TokenKind::Keyword(Keyword::For)]
+            }
+            // Everything an expression can start with.
+            Self::ExpectExpression => vec![
+                TokenKind::Identifiter,
+                TokenKind::Number(0_f64),
+                TokenKind::String(String::new()),
+                TokenKind::Keyword(Keyword::True),
+                TokenKind::Keyword(Keyword::False),
+                TokenKind::Keyword(Keyword::Nil),
+                TokenKind::Keyword(Keyword::This),
+                TokenKind::Keyword(Keyword::Super),
+                TokenKind::LeftParen,
+                TokenKind::Minus,
+                TokenKind::Bang,
+            ],
+            Self::InvalidAssignmentTarget
+            | Self::TooManyArguments
+            | Self::TooManyParameters
+            | Self::BreakOutsideLoop
+            | Self::ContinueOutsideLoop
+            | Self::UndefinedLabel(_)
+            | Self::IntegerTooLarge => Vec::new(),
+        }
+    }
+}
+
+/// The message without its final full stop, which `Error` adds after saying what was found.
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExpectClassName => write!(f, "Expect class name"),
+            Self::ExpectDotAfterSuper => write!(f, "Expect '.' after 'super'"),
+            Self::ExpectExpression => write!(f, "Expect expression"),
+            Self::ExpectFunctionName => write!(f, "Expect function name"),
+            Self::ExpectLeftBrace => write!(f, "Expect '{{' before body"),
+            Self::ExpectLeftParenAfterFunctionName => write!(f, "Expect '(' after function name"),
+            Self::ExpectLeftParenAfterIf => write!(f, "Expect '(' after 'if'"),
+            Self::ExpectLeftParenAfterWhile => write!(f, "Expect '(' after 'while'"),
+            Self::ExpectLeftParenAfterFor => write!(f, "Expect '(' after 'for'"),
+            Self::ExpectLoopAfterLabel => write!(f, "Expect a loop after label"),
+            Self::ExpectParameterName => write!(f, "Expect parameter name"),
+            Self::ExpectPropertyName => write!(f, "Expect property name after '.'"),
+            Self::ExpectRightBrace => write!(f, "Expect '}}' after block"),
+            Self::ExpectRightParenAfterExpression => write!(f, "Expect ')' after expression"),
+            Self::ExpectRightParenAfterParameters => write!(f, "Expect ')' after parameters"),
+            Self::ExpectRightParenAfterArguments => write!(f, "Expect ')' after arguments"),
+            Self::ExpectRightParenAfterCondition => write!(f, "Expect ')' after condition"),
+            Self::ExpectRightParenAfterForClauses => write!(f, "Expect ')' after for clauses"),
+            Self::ExpectSemicolonAfterExpression => write!(f, "Expect ';' after expression"),
+            Self::ExpectSemicolonAfterValue => write!(f, "Expect ';' after value"),
+            Self::ExpectSemicolonAfterVariable => write!(f, "Expect ';' after variable declaration"),
+            Self::ExpectSemicolonAfterReturnValue => write!(f, "Expect ';' after return value"),
+            Self::ExpectSemicolonAfterBreak => write!(f, "Expect ';' after 'break'"),
+            Self::ExpectSemicolonAfterContinue => write!(f, "Expect ';' after 'continue'"),
+            Self::ExpectSemicolonAfterLoopCondition => write!(f, "Expect ';' after loop condition"),
+            Self::ExpectSuperclassMethodName => write!(f, "Expect superclass method name"),
+            Self::ExpectSuperclassName => write!(f, "Expect superclass name"),
+            Self::ExpectVariableName => write!(f, "Expect variable name"),
+            Self::InvalidAssignmentTarget => write!(f, "Invalid assignment target"),
+            Self::TooManyArguments => write!(f, "Can't have more than {MAX_ARGUMENTS} arguments"),
+            Self::TooManyParameters => write!(f, "Can't have more than {MAX_ARGUMENTS} parameters"),
+            Self::BreakOutsideLoop => write!(f, "Can't use 'break' outside of a loop"),
+            Self::ContinueOutsideLoop => write!(f, "Can't use 'continue' outside of a loop"),
+            Self::UndefinedLabel(label) => write!(f, "No enclosing loop labeled '{label}'"),
+            Self::IntegerTooLarge => write!(f, "Integer literal is too large"),
+        }
+    }
 }
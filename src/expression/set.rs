@@ -0,0 +1,42 @@
+use super::Expr;
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SetExpression {
+    object: Box<Expr>,
+    name: Token,
+    value: Box<Expr>,
+}
+
+impl std::fmt::Display for SetExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{} = {}", self.object, self.name.lexeme, self.value)
+    }
+}
+
+impl SetExpression {
+    pub fn new(object: Expr, name: Token, value: Expr) -> Self {
+        Self {
+            object: Box::new(object),
+            name,
+            value: Box::new(value),
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        self.object.as_ref()
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Expr {
+        self.value.as_ref()
+    }
+
+    pub fn span(&self) -> Span {
+        self.object.span().to(self.value.span())
+    }
+}
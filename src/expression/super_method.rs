@@ -0,0 +1,50 @@
+use std::cell::Cell;
+
+use crate::span::Span;
+use crate::token::Token;
+
+/// A `super.method` access inside a subclass method.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SuperExpression {
+    keyword: Token,
+    method: Token,
+    depth: Cell<Option<usize>>,
+}
+
+impl std::fmt::Display for SuperExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "super.{}", self.method.lexeme)
+    }
+}
+
+impl SuperExpression {
+    pub fn new(keyword: Token, method: Token) -> Self {
+        Self {
+            keyword,
+            method,
+            depth: Cell::new(None),
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn method(&self) -> &Token {
+        &self.method
+    }
+
+    pub fn span(&self) -> Span {
+        self.keyword.span.to(self.method.span)
+    }
+
+    /// How many scopes out from the use the enclosing class bound `super`. `this` is always
+    /// bound one scope further in. Filled in by the resolver.
+    pub fn depth(&self) -> Option<usize> {
+        self.depth.get()
+    }
+
+    pub fn resolve(&self, depth: usize) {
+        self.depth.set(Some(depth));
+    }
+}
@@ -0,0 +1,43 @@
+use std::cell::Cell;
+
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ThisExpression {
+    keyword: Token,
+    depth: Cell<Option<usize>>,
+}
+
+impl std::fmt::Display for ThisExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "this")
+    }
+}
+
+impl ThisExpression {
+    pub fn new(keyword: Token) -> Self {
+        Self {
+            keyword,
+            depth: Cell::new(None),
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn span(&self) -> Span {
+        self.keyword.span
+    }
+
+    /// How many scopes out from the use the enclosing method bound `this`. Filled in by the
+    /// resolver.
+    pub fn depth(&self) -> Option<usize> {
+        self.depth.get()
+    }
+
+    pub fn resolve(&self, depth: usize) {
+        self.depth.set(Some(depth));
+    }
+}
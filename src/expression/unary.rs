@@ -1,9 +1,11 @@
 use super::Expr;
+use crate::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct UnaryExpression {
     operator :UnaryOperator,
-    right: Box<Expr>
+    right: Box<Expr>,
+    span: Span,
 }
 
 impl std::fmt::Display for UnaryExpression {
@@ -14,7 +16,7 @@ impl std::fmt::Display for UnaryExpression {
 
 impl UnaryExpression {
     fn new(operator: UnaryOperator, right: Expr) -> Self { 
-        Self { operator, right: Box::new(right) } 
+        Self { operator, span: right.span(), right: Box::new(right) } 
     }
 
     pub fn bang(right: Expr) -> Self {
@@ -33,6 +35,16 @@ impl UnaryExpression {
     pub fn operator(&self) -> &UnaryOperator {
         &self.operator
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Widens the span to take in the operator, which the constructors don't know about.
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
 }
 
 #[derive(PartialEq, Debug, Clone)]
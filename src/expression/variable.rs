@@ -0,0 +1,43 @@
+use std::cell::Cell;
+
+use crate::span::Span;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariableExpression {
+    name: Token,
+    depth: Cell<Option<usize>>,
+}
+
+impl std::fmt::Display for VariableExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name.lexeme)
+    }
+}
+
+impl VariableExpression {
+    pub fn new(name: Token) -> Self {
+        Self {
+            name,
+            depth: Cell::new(None),
+        }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn span(&self) -> Span {
+        self.name.span
+    }
+
+    /// How many scopes out from the use the variable was declared, or `None` for a global.
+    /// Filled in by the resolver.
+    pub fn depth(&self) -> Option<usize> {
+        self.depth.get()
+    }
+
+    pub fn resolve(&self, depth: usize) {
+        self.depth.set(Some(depth));
+    }
+}
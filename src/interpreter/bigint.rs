@@ -0,0 +1,57 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Zero};
+
+use super::{RuntimeErrorKind, Value};
+use crate::expression::BinaryOperator;
+
+/// Applies an integer operator to operands that don't fit in an `i64`, or whose result doesn't.
+/// Division and remainder round towards zero, like their `i64` counterparts.
+pub fn arithmetic(operator: BinaryOperator, left: &BigInt, right: &BigInt) -> Result<Value, RuntimeErrorKind> {
+    let result = match operator {
+        BinaryOperator::Add => left + right,
+        BinaryOperator::Sub => left - right,
+        BinaryOperator::Mult => left * right,
+        BinaryOperator::IntDiv | BinaryOperator::Mod if right.is_zero() => {
+            return Err(RuntimeErrorKind::DivisionByZero)
+        }
+        BinaryOperator::IntDiv => left / right,
+        BinaryOperator::Mod => left % right,
+        operator => unreachable!("'{operator}' isn't integer arithmetic"),
+    };
+    Ok(normalize(result))
+}
+
+/// Integers only stay at arbitrary precision while they don't fit in an `i64`, so that every
+/// integer has exactly one representation.
+pub fn normalize(x: BigInt) -> Value {
+    match i64::try_from(&x) {
+        Ok(x) => Value::Integer(x),
+        Err(_) => Value::BigInteger(Rc::new(x)),
+    }
+}
+
+/// Orders a big integer against a float without rounding either of them.
+pub fn compare_float(integer: &BigInt, float: f64) -> Option<Ordering> {
+    if float.is_nan() {
+        return None;
+    }
+    if float.is_infinite() {
+        return 0_f64.partial_cmp(&float);
+    }
+    // A whole float converts exactly; the fraction only matters when the whole parts are equal.
+    match integer.cmp(&BigInt::from_f64(float.trunc())?) {
+        Ordering::Equal => 0_f64.partial_cmp(&float.fract()),
+        ordering => Some(ordering),
+    }
+}
+
+pub fn widen(value: &Value) -> BigInt {
+    match value {
+        Value::Integer(x) => BigInt::from(*x),
+        Value::BigInteger(x) => BigInt::clone(x),
+        _ => unreachable!("only integers are widened"),
+    }
+}
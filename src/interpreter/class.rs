@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::{Function, RuntimeError, RuntimeErrorKind, Value};
+use crate::suggest;
+use crate::token::Token;
+
+/// A class as a runtime value. Calling it creates an instance.
+pub struct Class {
+    name: String,
+    superclass: Option<Rc<Class>>,
+    methods: HashMap<String, Rc<Function>>,
+}
+
+impl Class {
+    pub fn new(
+        name: String,
+        superclass: Option<Rc<Class>>,
+        methods: HashMap<String, Rc<Function>>,
+    ) -> Self {
+        Self {
+            name,
+            superclass,
+            methods,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Looks for `name` on this class first and then up the superclass chain.
+    pub fn find_method(&self, name: &str) -> Option<&Rc<Function>> {
+        self.methods.get(name).or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.find_method(name))
+        })
+    }
+
+    /// The names of every method on this class or inherited from its superclasses.
+    pub fn method_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.methods.keys().map(String::as_str).collect();
+        if let Some(superclass) = &self.superclass {
+            names.extend(superclass.method_names());
+        }
+        names
+    }
+
+    /// A class takes the arguments of its initializer, or none if it has no `init` method.
+    pub fn arity(&self) -> usize {
+        self.find_method("init").map_or(0, |init| init.arity())
+    }
+}
+
+/// Classes are only ever equal to themselves.
+impl PartialEq for Class {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::fmt::Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+pub struct Instance {
+    class: Rc<Class>,
+    fields: RefCell<HashMap<String, Value>>,
+}
+
+impl Instance {
+    pub fn new(class: Rc<Class>) -> Self {
+        Self {
+            class,
+            fields: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Fields shadow methods. Methods come back bound to this instance.
+    pub fn get(self: &Rc<Self>, name: &Token) -> Result<Value, RuntimeError> {
+        if let Some(value) = self.fields.borrow().get(&name.lexeme) {
+            return Ok(value.clone());
+        }
+        match self.class.find_method(&name.lexeme) {
+            Some(method) => {
+                let method = method.bind(Rc::clone(self));
+                Ok(Value::Callable(Rc::new(method)))
+            }
+            None => {
+                let fields = self.fields.borrow();
+                let names = fields.keys().map(String::as_str).chain(self.class.method_names());
+                let suggestion = suggest::closest(&name.lexeme, names).map(str::to_string);
+                let kind = RuntimeErrorKind::UndefinedProperty {
+                    name: name.lexeme.clone(),
+                    suggestion,
+                };
+                Err(kind.at(name.span))
+            }
+        }
+    }
+
+    pub fn set(&self, name: &Token, value: Value) {
+        self.fields.borrow_mut().insert(name.lexeme.clone(), value);
+    }
+}
+
+/// Instances are only ever equal to themselves.
+impl PartialEq for Instance {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::fmt::Display for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} instance", self.class.name)
+    }
+}
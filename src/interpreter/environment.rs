@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::{RuntimeError, RuntimeErrorKind};
+use super::Value;
+use crate::suggest;
+use crate::token::Token;
+
+#[derive(Debug, Default)]
+pub struct Environment {
+    values: HashMap<String, Value>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    /// Creates a scope nested inside `enclosing`; lookups that miss here fall through to it.
+    pub fn with_enclosing(enclosing: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            values: HashMap::new(),
+            enclosing: Some(enclosing),
+        }
+    }
+
+    /// Binds `name` to `value` in this scope, silently replacing any previous binding.
+    pub fn define(&mut self, name: &str, value: Value) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn get(&self, name: &Token) -> Result<Value, RuntimeError> {
+        self.lookup(&name.lexeme).ok_or_else(|| self.undefined(name))
+    }
+
+    fn lookup(&self, name: &str) -> Option<Value> {
+        if let Some(value) = self.values.get(name) {
+            return Some(value.clone());
+        }
+        self.enclosing
+            .as_ref()
+            .and_then(|enclosing| enclosing.borrow().lookup(name))
+    }
+
+    /// Looks `name` up exactly `depth` scopes out, as computed by the resolver.
+    pub fn get_at(&self, depth: usize, name: &Token) -> Result<Value, RuntimeError> {
+        self.value_at(depth, &name.lexeme)
+            .ok_or_else(|| self.undefined(name))
+    }
+
+    pub fn value_at(&self, depth: usize, name: &str) -> Option<Value> {
+        if depth == 0 {
+            return self.values.get(name).cloned();
+        }
+        self.enclosing
+            .as_ref()
+            .and_then(|enclosing| enclosing.borrow().value_at(depth - 1, name))
+    }
+
+    pub fn assign_at(&mut self, depth: usize, name: &Token, value: Value) -> Result<(), RuntimeError> {
+        if self.store(depth, &name.lexeme, value) {
+            return Ok(());
+        }
+        Err(self.undefined(name))
+    }
+
+    fn store(&mut self, depth: usize, name: &str, value: Value) -> bool {
+        if depth == 0 {
+            return match self.values.get_mut(name) {
+                Some(slot) => {
+                    *slot = value;
+                    true
+                }
+                None => false,
+            };
+        }
+        self.enclosing
+            .as_ref()
+            .is_some_and(|enclosing| enclosing.borrow_mut().store(depth - 1, name, value))
+    }
+
+    /// Assigning never creates a new binding; the variable must already exist in some scope.
+    pub fn assign(&mut self, name: &Token, value: Value) -> Result<(), RuntimeError> {
+        if self.store_innermost(&name.lexeme, value) {
+            return Ok(());
+        }
+        Err(self.undefined(name))
+    }
+
+    fn store_innermost(&mut self, name: &str, value: Value) -> bool {
+        if let Some(slot) = self.values.get_mut(name) {
+            *slot = value;
+            return true;
+        }
+        self.enclosing
+            .as_ref()
+            .is_some_and(|enclosing| enclosing.borrow_mut().store_innermost(name, value))
+    }
+
+    /// The error for using `name` when it isn't defined, suggesting the closest name that is.
+    pub fn undefined(&self, name: &Token) -> RuntimeError {
+        let mut names = Vec::new();
+        self.collect_names(&mut names);
+        let suggestion = suggest::closest(&name.lexeme, names.iter().map(String::as_str));
+        let kind = RuntimeErrorKind::UndefinedVariable {
+            name: name.lexeme.clone(),
+            suggestion: suggestion.map(str::to_string),
+        };
+        kind.at(name.span)
+    }
+
+    fn collect_names(&self, names: &mut Vec<String>) {
+        names.extend(self.values.keys().cloned());
+        if let Some(enclosing) = &self.enclosing {
+            enclosing.borrow().collect_names(names);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::environment::Environment;
+use super::{Instance, Value};
+use crate::statement::{FunctionStatement, Stmt};
+
+/// A user-defined function as a runtime value, together with the scope it was declared in.
+pub struct Function {
+    declaration: Rc<FunctionStatement>,
+    closure: Rc<RefCell<Environment>>,
+    is_initializer: bool,
+}
+
+impl Function {
+    /// The closure is held by reference rather than copied, so the function sees later writes to
+    /// captured variables and keeps them alive after the declaring scope has been exited.
+    pub fn new(declaration: Rc<FunctionStatement>, closure: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            declaration,
+            closure,
+            is_initializer: false,
+        }
+    }
+
+    /// Creates a method. A method named `init` is its class's initializer, and calling it
+    /// always yields the instance.
+    pub fn method(declaration: Rc<FunctionStatement>, closure: Rc<RefCell<Environment>>) -> Self {
+        let is_initializer = declaration.name().lexeme == "init";
+        Self {
+            declaration,
+            closure,
+            is_initializer,
+        }
+    }
+
+    /// Returns a copy of this method whose `this` refers to `instance`.
+    pub fn bind(&self, instance: Rc<Instance>) -> Self {
+        let mut environment = Environment::with_enclosing(Rc::clone(&self.closure));
+        environment.define("this", Value::Instance(instance));
+        Self {
+            declaration: Rc::clone(&self.declaration),
+            closure: Rc::new(RefCell::new(environment)),
+            is_initializer: self.is_initializer,
+        }
+    }
+
+    pub fn is_initializer(&self) -> bool {
+        self.is_initializer
+    }
+
+    /// The instance a bound method was bound to.
+    pub fn this(&self) -> Option<Value> {
+        self.closure.borrow().value_at(0, "this")
+    }
+
+    pub fn closure(&self) -> &Rc<RefCell<Environment>> {
+        &self.closure
+    }
+
+    pub fn name(&self) -> &str {
+        &self.declaration.name().lexeme
+    }
+
+    pub fn arity(&self) -> usize {
+        self.declaration.params().len()
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = &str> {
+        self.declaration.params().iter().map(|x| x.lexeme.as_str())
+    }
+
+    pub fn body(&self) -> &[Stmt] {
+        self.declaration.body()
+    }
+}
+
+/// Functions are only ever equal to themselves.
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::fmt::Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<fn {}>", self.name())
+    }
+}
@@ -1,129 +1,709 @@
+use crate::expression::{Expr, UnaryExpression, BinaryExpression, AssignExpression, CallExpression, GetExpression, LogicalExpression, LogicalOperator, SetExpression, SuperExpression};
+use crate::span::Span;
+use crate::suggest;
+use crate::token::Token;
+use crate::statement::{ClassStatement, FunctionStatement, IfStatement, ReturnStatement, Stmt, VarStatement, WhileStatement};
 
-#![allow(dead_code)]
-use crate::expression::{Expr, UnaryExpression, LiteralExpression, LiteralOperator, BinaryExpression};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+#[cfg(feature = "bigint")]
+mod bigint;
+mod class;
+mod environment;
+mod function;
+mod value;
+
+use std::collections::HashMap;
+
+pub use self::class::{Class, Instance};
+use self::environment::Environment;
+pub use self::function::Function;
+pub use self::value::Value;
 
 #[cfg(test)]
 mod tests;
 
-#[derive(Debug, PartialEq)]
-enum RuntimeError {
-    NumericOperandExpected(LiteralExpression),
-    LiteralOperandExpected,
-}
+/// How deeply calls may nest before the program is stopped with a stack overflow.
+pub const MAX_CALL_DEPTH: usize = 1000;
 
-fn evaluate(expr: Expr) -> Result<Expr, RuntimeError> {
-    let result = match expr {
-        Expr::Arithmetic(e) => binary(e)?,
-        Expr::Compare(e) => binary(e)?,
-        Expr::Equality(e) => binary(e)?,
-        Expr::Grouping(e) => evaluate(*e)?,
-        Expr::Literal(e) => Expr::Literal(e),
-        Expr::Unary(e) => unary(e)?,
-    };
-    Ok(result)
+/// Enough stack for the interpreter to reach `MAX_CALL_DEPTH`, with room to spare even in
+/// debug builds. The main thread's stack is far too small for that.
+pub const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// An error raised while running a program, located at the expression that caused it.
+#[derive(Debug, PartialEq)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub span: Span,
+    /// The calls the error unwound through, innermost first.
+    pub trace: Vec<Frame>,
 }
 
-fn unary(expr: UnaryExpression) -> Result<Expr, RuntimeError> {
-    let right = evaluate(expr.right().clone())?;
-    let literal = match expr.operator() {
-        crate::expression::UnaryOperator::Bang => LiteralExpression::boolean(!evaluate_boolean_literal(&right)),
-        crate::expression::UnaryOperator::Minus => {
-            let number = expect_numeric_literal(&right)?;
-            LiteralExpression::number(-number)
-        },
-    };
-    Ok(Expr::Literal(literal))
+/// A call that was still running when an error was raised.
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    pub function: String,
+    /// The call expression that started it.
+    pub call: Span,
 }
 
-fn evaluate_boolean_literal(expr: &Expr) -> bool {
-    match expr {
-        Expr::Literal(l) => {
-            match l.value() {
-                LiteralOperator::Boolean(b) => *b,
-                LiteralOperator::Nil => false,
-                LiteralOperator::Number(_) => true,
-                LiteralOperator::String(_) => true,
+impl RuntimeError {
+    /// A hint at how to fix the error, such as the name a misspelled one resembles.
+    pub fn help(&self) -> Option<String> {
+        match &self.kind {
+            RuntimeErrorKind::UndefinedVariable { suggestion, .. }
+            | RuntimeErrorKind::UndefinedProperty { suggestion, .. } => {
+                suggestion.as_deref().map(suggest::did_you_mean)
             }
+            _ => None,
         }
-        _ => true,
+    }
+
+    /// Records that the error escaped from a call to `function` made at `call`.
+    fn called_from(mut self, function: &str, call: Span) -> Self {
+        self.trace.push(Frame {
+            function: function.to_string(),
+            call,
+        });
+        self
+    }
+
+    /// Where each active frame was when the error was raised, innermost first, ending with the
+    /// top-level script. Functions are named, the script is `None`.
+    pub fn stack(&self) -> impl Iterator<Item = (Option<&str>, Span)> {
+        let functions = self.trace.iter().map(|x| Some(x.function.as_str()));
+        let spans = self.trace.iter().map(|x| x.call);
+        functions
+            .chain(std::iter::once(None))
+            .zip(std::iter::once(self.span).chain(spans))
     }
 }
 
-fn expect_numeric_literal(expr: &Expr) -> Result<&f64, RuntimeError> {
-    let literal = expr.as_literal().ok_or(RuntimeError::LiteralOperandExpected)?;
-    let literal = literal.value()
-        .as_number()
-        .ok_or(
-            RuntimeError::NumericOperandExpected(literal.to_owned())
-        )?;
-    Ok(literal)
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[line {}:{}] Error: {}", self.span.line, self.span.column, self.kind)
+    }
 }
 
-fn binary(expr: BinaryExpression) -> Result<Expr, RuntimeError> {
-    let result = match expr.operator() {
-        crate::expression::BinaryOperator::Mult => mult(expr),
-        crate::expression::BinaryOperator::Div => div(expr),
-        crate::expression::BinaryOperator::Add => add(expr),
-        crate::expression::BinaryOperator::Sub => sub(expr),
-        crate::expression::BinaryOperator::Greater => greater(expr),
-        crate::expression::BinaryOperator::Less => less(expr),
-        crate::expression::BinaryOperator::GreaterEqual => greater_equal(expr),
-        crate::expression::BinaryOperator::LessEqual => less_equal(expr),
-        crate::expression::BinaryOperator::Equal => equal(expr),
-        crate::expression::BinaryOperator::NotEqual => not_equal(expr),
-    };
-    Ok(Expr::Literal(result?))
+#[derive(Debug, PartialEq)]
+pub enum RuntimeErrorKind {
+    NumericOperandExpected(Value),
+    /// `suggestion` is the closest name that is defined, if any is close enough.
+    UndefinedVariable { name: String, suggestion: Option<String> },
+    NotCallable(Value),
+    UndefinedProperty { name: String, suggestion: Option<String> },
+    OnlyInstancesHaveProperties(Value),
+    SuperclassMustBeClass(Value),
+    ArityMismatch { name: String, expected: usize, found: usize },
+    Output(std::io::ErrorKind),
+    AddOperandsMismatch(Value, Value),
+    IntegerOverflow,
+    DivisionByZero,
+    StackOverflow,
 }
 
-fn binary_operation<T>(expr: BinaryExpression, operation: &dyn Fn(&f64, &f64) -> T) -> Result<T, RuntimeError> {
-    let left = evaluate(expr.left().clone())?;
-    let right = evaluate(expr.right().clone())?;
+impl RuntimeErrorKind {
+    pub fn at(self, span: Span) -> RuntimeError {
+        RuntimeError {
+            kind: self,
+            span,
+            trace: Vec::new(),
+        }
+    }
 
-    let left = expect_numeric_literal(&left)?;
-    let right = expect_numeric_literal(&right)?;
-    Ok(operation(left, right))
+    /// A stable identifier for the kind of error, for tools that consume diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NumericOperandExpected(_) => "E0401",
+            Self::UndefinedVariable { .. } => "E0403",
+            Self::NotCallable(_) => "E0404",
+            Self::UndefinedProperty { .. } => "E0405",
+            Self::OnlyInstancesHaveProperties(_) => "E0406",
+            Self::SuperclassMustBeClass(_) => "E0407",
+            Self::ArityMismatch { .. } => "E0408",
+            Self::Output(_) => "E0409",
+            Self::AddOperandsMismatch(..) => "E0410",
+            Self::IntegerOverflow => "E0411",
+            Self::DivisionByZero => "E0412",
+            Self::StackOverflow => "E0413",
+        }
+    }
 }
 
-fn mult(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-    Ok(LiteralExpression::number(binary_operation(expr, &|left, right| left * right )?))
+impl std::fmt::Display for RuntimeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NumericOperandExpected(literal) => write!(f, "Operand must be a number, found '{literal}'."),
+            Self::UndefinedVariable { name, .. } => write!(f, "Undefined variable '{name}'."),
+            Self::NotCallable(literal) => write!(f, "Can only call functions and classes, found '{literal}'."),
+            Self::UndefinedProperty { name, .. } => write!(f, "Undefined property '{name}'."),
+            Self::OnlyInstancesHaveProperties(literal) => {
+                write!(f, "Only instances have properties, found '{literal}'.")
+            }
+            Self::SuperclassMustBeClass(literal) => {
+                write!(f, "Superclass must be a class, found '{literal}'.")
+            }
+            Self::ArityMismatch { name, expected, found } => {
+                write!(f, "Expected {expected} arguments but got {found} in call to '{name}'.")
+            }
+            Self::Output(kind) => write!(f, "Failed to write output: {kind}."),
+            Self::AddOperandsMismatch(left, right) => write!(
+                f,
+                "Operands must be two numbers or two strings, found '{left}' and '{right}'."
+            ),
+            Self::IntegerOverflow => write!(f, "Integer overflow."),
+            Self::DivisionByZero => write!(f, "Integer division by zero."),
+            Self::StackOverflow => write!(f, "Stack overflow."),
+        }
+    }
 }
 
-fn div(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::number(binary_operation(expr, &|left, right| left / right )?)) 
+/// Why execution of a statement stopped early. Loop jumps travel through the same channel
+/// as errors so that every enclosing block restores its scope on the way out.
+#[derive(Debug, PartialEq)]
+enum Unwind {
+    Error(RuntimeError),
+    Break(Option<String>),
+    Continue(Option<String>),
+    Return(Value),
 }
 
-fn add(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::number(binary_operation(expr, &|left, right| left + right )?)) 
+impl From<RuntimeError> for Unwind {
+    fn from(err: RuntimeError) -> Self {
+        Self::Error(err)
+    }
 }
 
-fn sub(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::number(binary_operation(expr, &|left, right| left - right )?)) 
+pub struct Interpreter<W: std::io::Write> {
+    globals: Rc<RefCell<Environment>>,
+    environment: Rc<RefCell<Environment>>,
+    out: W,
+    string_coercion: bool,
+    /// How many calls are currently running.
+    depth: usize,
 }
 
-fn greater(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::boolean(binary_operation(expr, &|left, right| left > right )?)) 
-}
+impl<W: std::io::Write> Interpreter<W> {
+    pub fn new(out: W) -> Self {
+        let globals = Rc::new(RefCell::new(Environment::default()));
+        Self {
+            environment: Rc::clone(&globals),
+            globals,
+            out,
+            string_coercion: false,
+            depth: 0,
+        }
+    }
+
+    /// Turns on an extension to Lox where adding a number and a string converts the number to
+    /// a string, so that `"n = " + 3` is `"n = 3"` rather than an error.
+    pub fn with_string_coercion(mut self, enabled: bool) -> Self {
+        self.string_coercion = enabled;
+        self
+    }
+
+    pub fn interpret(&mut self, statements: &[Stmt]) -> Result<(), RuntimeError> {
+        for statement in statements {
+            match self.execute(statement) {
+                Ok(()) => {}
+                Err(Unwind::Error(err)) => return Err(err),
+                Err(Unwind::Return(_)) => unreachable!("the resolver rejects top-level returns"),
+                Err(Unwind::Break(_) | Unwind::Continue(_)) => {
+                    unreachable!("the parser rejects jumps outside of loops")
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, stmt: &Stmt) -> Result<(), Unwind> {
+        match stmt {
+            Stmt::Expression(expr) => {
+                self.evaluate(expr)?;
+            }
+            Stmt::Print(expr) => {
+                let value = self.evaluate(expr)?;
+                writeln!(self.out, "{value}")
+                    .map_err(|err| RuntimeErrorKind::Output(err.kind()).at(expr.span()))?;
+            }
+            Stmt::Var(stmt) => self.var(stmt)?,
+            Stmt::If(stmt) => self.if_else(stmt)?,
+            Stmt::Function(stmt) => self.function(stmt),
+            Stmt::Class(stmt) => self.class(stmt)?,
+            Stmt::Return(stmt) => return Err(self.return_statement(stmt)?),
+            Stmt::While(stmt) => self.while_loop(stmt)?,
+            Stmt::Block(statements) => {
+                let environment = Environment::with_enclosing(Rc::clone(&self.environment));
+                self.execute_block(statements, environment)?;
+            }
+            Stmt::Break(stmt) => return Err(Unwind::Break(stmt.label().map(|x| x.lexeme.clone()))),
+            Stmt::Continue(stmt) => return Err(Unwind::Continue(stmt.label().map(|x| x.lexeme.clone()))),
+        }
+        Ok(())
+    }
+
+    /// Runs `statements` inside `environment`, restoring the current scope afterwards even
+    /// when one of them fails.
+    fn execute_block(&mut self, statements: &[Stmt], environment: Environment) -> Result<(), Unwind> {
+        let previous = std::mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+        let result = statements.iter().try_for_each(|statement| self.execute(statement));
+        self.environment = previous;
+        result
+    }
+
+    fn function(&mut self, stmt: &Rc<FunctionStatement>) {
+        let function = Function::new(Rc::clone(stmt), Rc::clone(&self.environment));
+        let value = Value::Callable(Rc::new(function));
+        self.environment.borrow_mut().define(&stmt.name().lexeme, value);
+    }
+
+    fn class(&mut self, stmt: &ClassStatement) -> Result<(), RuntimeError> {
+        let superclass = match stmt.superclass() {
+            Some(superclass) => {
+                match self.evaluate(&Expr::Variable(superclass.clone()))? {
+                    Value::Class(class) => Some(class),
+                    value => {
+                        let kind = RuntimeErrorKind::SuperclassMustBeClass(value);
+                        return Err(kind.at(superclass.span()));
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Methods of a subclass close over an extra scope binding `super`.
+        let enclosing = Rc::clone(&self.environment);
+        if let Some(superclass) = &superclass {
+            let mut environment = Environment::with_enclosing(Rc::clone(&enclosing));
+            let value = Value::Class(Rc::clone(superclass));
+            environment.define("super", value);
+            self.environment = Rc::new(RefCell::new(environment));
+        }
+
+        let methods: HashMap<String, Rc<Function>> = stmt
+            .methods()
+            .iter()
+            .map(|method| {
+                let function = Function::method(Rc::clone(method), Rc::clone(&self.environment));
+                (method.name().lexeme.clone(), Rc::new(function))
+            })
+            .collect();
+        self.environment = enclosing;
+
+        let class = Class::new(stmt.name().lexeme.clone(), superclass, methods);
+        let value = Value::Class(Rc::new(class));
+        self.environment.borrow_mut().define(&stmt.name().lexeme, value);
+        Ok(())
+    }
+
+    fn return_statement(&mut self, stmt: &ReturnStatement) -> Result<Unwind, RuntimeError> {
+        let value = match stmt.value() {
+            Some(value) => self.evaluate(value)?,
+            None => Value::Nil,
+        };
+        Ok(Unwind::Return(value))
+    }
 
-fn less(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::boolean(binary_operation(expr, &|left, right| left < right )?)) 
+    fn if_else(&mut self, stmt: &IfStatement) -> Result<(), Unwind> {
+        let condition = self.evaluate(stmt.condition())?;
+        if condition.is_truthy() {
+            self.execute(stmt.then_branch())
+        } else if let Some(else_branch) = stmt.else_branch() {
+            self.execute(else_branch)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn while_loop(&mut self, stmt: &WhileStatement) -> Result<(), Unwind> {
+        while self.evaluate(stmt.condition())?.is_truthy() {
+            match self.execute(stmt.body()) {
+                Err(Unwind::Break(label)) if stmt.is_target(label.as_deref()) => break,
+                Err(Unwind::Continue(label)) if stmt.is_target(label.as_deref()) => {}
+                result => result?,
+            }
+            if let Some(increment) = stmt.increment() {
+                self.evaluate(increment)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn var(&mut self, stmt: &VarStatement) -> Result<(), RuntimeError> {
+        let value = match stmt.initializer() {
+            Some(initializer) => self.evaluate(initializer)?,
+            None => Value::Nil,
+        };
+        self.environment.borrow_mut().define(&stmt.name().lexeme, value);
+        Ok(())
+    }
+
+    fn evaluate(&mut self, expr: &Expr) -> Result<Value, RuntimeError> {
+        let result = match expr {
+            Expr::Arithmetic(e) => self.binary(e)?,
+            Expr::Assign(e) => self.assign(e)?,
+            Expr::Call(e) => self.call(e)?,
+            Expr::Get(e) => self.get(e)?,
+            Expr::Set(e) => self.set(e)?,
+            Expr::Super(e) => self.super_method(e)?,
+            Expr::This(e) => match e.depth() {
+                Some(depth) => self.environment.borrow().get_at(depth, e.keyword())?,
+                None => self.globals.borrow().get(e.keyword())?,
+            },
+            Expr::Compare(e) => self.binary(e)?,
+            Expr::Equality(e) => self.binary(e)?,
+            Expr::Grouping(e) => self.evaluate(e.expression())?,
+            Expr::Literal(e) => Value::from(e.value()),
+            Expr::Logical(e) => self.logical(e)?,
+            Expr::Unary(e) => self.unary(e)?,
+            Expr::Variable(e) => match e.depth() {
+                Some(depth) => self.environment.borrow().get_at(depth, e.name())?,
+                None => self.global(e.name())?,
+            },
+        };
+        Ok(result)
+    }
+
+    /// Looks up a variable the resolver left to be found among the globals. If it isn't there,
+    /// the error suggests names from every scope in reach, not just the global one.
+    fn global(&self, name: &Token) -> Result<Value, RuntimeError> {
+        let value = self.globals.borrow().get(name);
+        value.map_err(|_| self.environment.borrow().undefined(name))
+    }
+
+    fn call(&mut self, expr: &CallExpression) -> Result<Value, RuntimeError> {
+        let callee = self.evaluate(expr.callee())?;
+        let arguments = expr
+            .arguments()
+            .iter()
+            .map(|argument| self.evaluate(argument))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (name, arity) = match &callee {
+            Value::Callable(function) => (function.name(), function.arity()),
+            Value::Class(class) => (class.name(), class.arity()),
+            _ => {
+                let kind = RuntimeErrorKind::NotCallable(callee.clone());
+                return Err(kind.at(expr.callee().span()));
+            }
+        };
+        if arguments.len() != arity {
+            let kind = RuntimeErrorKind::ArityMismatch {
+                name: name.to_string(),
+                expected: arity,
+                found: arguments.len(),
+            };
+            return Err(kind.at(expr.span()));
+        }
+        if self.depth == MAX_CALL_DEPTH {
+            return Err(RuntimeErrorKind::StackOverflow.at(expr.span()));
+        }
+        self.depth += 1;
+        let result = match &callee {
+            Value::Class(class) => self.instantiate(class, arguments),
+            Value::Callable(function) => self.call_function(function, arguments),
+            _ => unreachable!("only functions and classes have an arity"),
+        };
+        self.depth -= 1;
+        result.map_err(|err| err.called_from(name, expr.span()))
+    }
+
+    /// Creates an instance of `class` and runs its initializer, if any, on it.
+    fn instantiate(&mut self, class: &Rc<Class>, arguments: Vec<Value>) -> Result<Value, RuntimeError> {
+        let instance = Rc::new(Instance::new(Rc::clone(class)));
+        if let Some(init) = class.find_method("init") {
+            self.call_function(&init.bind(Rc::clone(&instance)), arguments)?;
+        }
+        Ok(Value::Instance(instance))
+    }
+
+    fn get(&mut self, expr: &GetExpression) -> Result<Value, RuntimeError> {
+        match self.evaluate(expr.object())? {
+            Value::Instance(instance) => instance.get(expr.name()),
+            object => {
+                let kind = RuntimeErrorKind::OnlyInstancesHaveProperties(object);
+                Err(kind.at(expr.object().span()))
+            }
+        }
+    }
+
+    /// Looks a method up on the superclass and binds it to the current `this`.
+    fn super_method(&mut self, expr: &SuperExpression) -> Result<Value, RuntimeError> {
+        let depth = expr.depth().expect("the resolver binds every 'super'");
+        let superclass = self.environment.borrow().get_at(depth, expr.keyword())?;
+        let this = self.environment.borrow().value_at(depth - 1, "this");
+        let (Value::Class(superclass), Some(Value::Instance(instance))) = (superclass, this) else {
+            unreachable!("the resolver only allows 'super' inside subclass methods");
+        };
+        let name = &expr.method().lexeme;
+        let method = superclass.find_method(name).ok_or_else(|| {
+            let suggestion = suggest::closest(name, superclass.method_names()).map(str::to_string);
+            RuntimeErrorKind::UndefinedProperty { name: name.clone(), suggestion }.at(expr.method().span)
+        })?;
+        Ok(Value::Callable(Rc::new(method.bind(instance))))
+    }
+
+    fn set(&mut self, expr: &SetExpression) -> Result<Value, RuntimeError> {
+        let instance = match self.evaluate(expr.object())? {
+            Value::Instance(instance) => instance,
+            object => {
+                let kind = RuntimeErrorKind::OnlyInstancesHaveProperties(object);
+                return Err(kind.at(expr.object().span()));
+            }
+        };
+        let value = self.evaluate(expr.value())?;
+        instance.set(expr.name(), value.clone());
+        Ok(value)
+    }
+
+    /// Runs the body of `function` in a fresh scope holding its arguments, nested inside the
+    /// scope the function was declared in.
+    fn call_function(&mut self, function: &Function, arguments: Vec<Value>) -> Result<Value, RuntimeError> {
+        let mut environment = Environment::with_enclosing(Rc::clone(function.closure()));
+        for (param, argument) in function.params().zip(arguments) {
+            environment.define(param, argument);
+        }
+        let result = self.execute_block(function.body(), environment);
+        if function.is_initializer() {
+            // `init()` hands back the instance however it returns, even when called directly.
+            return match result {
+                Ok(()) | Err(Unwind::Return(_)) => {
+                    Ok(function.this().expect("initializers are always bound"))
+                }
+                Err(Unwind::Error(err)) => Err(err),
+                Err(Unwind::Break(_) | Unwind::Continue(_)) => {
+                    unreachable!("the parser rejects jumps outside of loops")
+                }
+            };
+        }
+        match result {
+            Ok(()) => Ok(Value::Nil),
+            Err(Unwind::Return(value)) => Ok(value),
+            Err(Unwind::Error(err)) => Err(err),
+            Err(Unwind::Break(_) | Unwind::Continue(_)) => {
+                unreachable!("the parser rejects jumps outside of loops")
+            }
+        }
+    }
+
+    fn assign(&mut self, expr: &AssignExpression) -> Result<Value, RuntimeError> {
+        let value = self.evaluate(expr.value())?;
+        match expr.depth() {
+            Some(depth) => self.environment.borrow_mut().assign_at(depth, expr.name(), value.clone())?,
+            None => {
+                let assigned = self.globals.borrow_mut().assign(expr.name(), value.clone());
+                assigned.map_err(|_| self.environment.borrow().undefined(expr.name()))?
+            }
+        }
+        Ok(value)
+    }
+
+    /// Short-circuits and yields whichever operand decided the result, not a coerced boolean.
+    fn logical(&mut self, expr: &LogicalExpression) -> Result<Value, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let decided = match expr.operator() {
+            LogicalOperator::Or => left.is_truthy(),
+            LogicalOperator::And => !left.is_truthy(),
+        };
+        if decided {
+            return Ok(left);
+        }
+        self.evaluate(expr.right())
+    }
+
+    fn unary(&mut self, expr: &UnaryExpression) -> Result<Value, RuntimeError> {
+        let right = self.evaluate(expr.right())?;
+        let value = match expr.operator() {
+            crate::expression::UnaryOperator::Bang => Value::Boolean(!right.is_truthy()),
+            crate::expression::UnaryOperator::Minus => match right {
+                Value::Integer(x) => match x.checked_neg() {
+                    Some(x) => Value::Integer(x),
+                    #[cfg(feature = "bigint")]
+                    None => bigint::normalize(-num_bigint::BigInt::from(x)),
+                    #[cfg(not(feature = "bigint"))]
+                    None => return Err(RuntimeErrorKind::IntegerOverflow.at(expr.span())),
+                },
+                #[cfg(feature = "bigint")]
+                Value::BigInteger(x) => bigint::normalize(-x.as_ref()),
+                _ => Value::Number(-expect_number(&right, expr.right().span())?),
+            },
+        };
+        Ok(value)
+    }
+
+    fn binary(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        match expr.operator() {
+            crate::expression::BinaryOperator::Mult => self.mult(expr),
+            crate::expression::BinaryOperator::Div => self.div(expr),
+            crate::expression::BinaryOperator::IntDiv => self.int_div(expr),
+            crate::expression::BinaryOperator::Mod => self.modulo(expr),
+            crate::expression::BinaryOperator::Add => self.add(expr),
+            crate::expression::BinaryOperator::Sub => self.sub(expr),
+            crate::expression::BinaryOperator::Greater => self.greater(expr),
+            crate::expression::BinaryOperator::Less => self.less(expr),
+            crate::expression::BinaryOperator::GreaterEqual => self.greater_equal(expr),
+            crate::expression::BinaryOperator::LessEqual => self.less_equal(expr),
+            crate::expression::BinaryOperator::Equal => self.equal(expr),
+            crate::expression::BinaryOperator::NotEqual => self.not_equal(expr),
+        }
+    }
+
+    fn binary_operation<T>(&mut self, expr: &BinaryExpression, operation: &dyn Fn(f64, f64) -> T) -> Result<T, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let right = self.evaluate(expr.right())?;
+
+        let left = expect_number(&left, expr.left().span())?;
+        let right = expect_number(&right, expr.right().span())?;
+        Ok(operation(left, right))
+    }
+
+    /// Applies `integer` when both operands are integers, and `float` as soon as either one is
+    /// a float.
+    fn arithmetic(
+        &mut self,
+        expr: &BinaryExpression,
+        integer: fn(i64, i64) -> Result<i64, RuntimeErrorKind>,
+        float: fn(f64, f64) -> f64,
+    ) -> Result<Value, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let right = self.evaluate(expr.right())?;
+        arithmetic(expr, &left, &right, integer, float)
+    }
+
+    /// Compares numbers by their exact values, integers and floats alike. Nothing is ordered
+    /// against `NaN`.
+    fn comparison(&mut self, expr: &BinaryExpression, test: fn(Ordering) -> bool) -> Result<Value, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let right = self.evaluate(expr.right())?;
+        expect_number(&left, expr.left().span())?;
+        expect_number(&right, expr.right().span())?;
+        Ok(Value::Boolean(left.compare(&right).is_some_and(test)))
+    }
+
+    fn mult(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.arithmetic(expr, |l, r| overflow(l.checked_mul(r)), |l, r| l * r)
+    }
+
+    /// Always divides as floats, so that `1 / 2` is `0.5`; `~/` is integer division.
+    fn div(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+       Ok(Value::Number(self.binary_operation(expr, &|left, right| left / right )?)) 
+    }
+
+    /// Divides and drops the fractional part, rounding towards zero.
+    fn int_div(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.arithmetic(expr, |l, r| overflow(l.checked_div(nonzero(r)?)), |l, r| (l / r).trunc())
+    }
+
+    /// The remainder of `~/`, which takes the sign of the dividend. It never overflows: the
+    /// only case `checked_rem` rejects besides zero, `i64::MIN % -1`, is simply 0.
+    fn modulo(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.arithmetic(expr, |l, r| Ok(l.wrapping_rem(nonzero(r)?)), |l, r| l % r)
+    }
+
+    /// Adds two numbers or concatenates two strings.
+    fn add(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let right = self.evaluate(expr.right())?;
+        let is_number = |x: &Value| x.as_number().is_some();
+        match (&left, &right) {
+            (Value::String(_), Value::String(_)) => Ok(Value::String(format!("{left}{right}").into())),
+            (Value::String(_), x) | (x, Value::String(_)) if self.string_coercion && is_number(x) => {
+                Ok(Value::String(format!("{left}{right}").into()))
+            }
+            (l, r) if is_number(l) && is_number(r) => {
+                arithmetic(expr, &left, &right, |l, r| overflow(l.checked_add(r)), |l, r| l + r)
+            }
+            _ => Err(RuntimeErrorKind::AddOperandsMismatch(left, right).at(expr.span())),
+        }
+    }
+
+    fn sub(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.arithmetic(expr, |l, r| overflow(l.checked_sub(r)), |l, r| l - r)
+    }
+
+    fn greater(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.comparison(expr, Ordering::is_gt)
+    }
+
+    fn less(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.comparison(expr, Ordering::is_lt)
+    }
+
+    fn greater_equal(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.comparison(expr, Ordering::is_ge)
+    }
+
+    fn less_equal(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        self.comparison(expr, Ordering::is_le)
+    }
+
+    fn equal(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let right = self.evaluate(expr.right())?;
+        Ok(Value::Boolean(left.is_equal(&right)))
+    }
+
+    fn not_equal(&mut self, expr: &BinaryExpression) -> Result<Value, RuntimeError> {
+        let left = self.evaluate(expr.left())?;
+        let right = self.evaluate(expr.right())?;
+        Ok(Value::Boolean(!left.is_equal(&right)))
+    }
 }
 
-fn greater_equal(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::boolean(binary_operation(expr, &|left, right| left >= right )?)) 
+/// The promotion rule for arithmetic: two integers give an integer, checked for overflow, and
+/// anything involving a float gives a float. With the `bigint` feature an integer that
+/// overflows grows instead.
+fn arithmetic(
+    expr: &BinaryExpression,
+    left: &Value,
+    right: &Value,
+    integer: fn(i64, i64) -> Result<i64, RuntimeErrorKind>,
+    float: fn(f64, f64) -> f64,
+) -> Result<Value, RuntimeError> {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => {
+            let result = integer(*l, *r).map(Value::Integer);
+            // With arbitrary precision, overflowing only means the result needs more room.
+            #[cfg(feature = "bigint")]
+            let result = match result {
+                Err(RuntimeErrorKind::IntegerOverflow) => {
+                    bigint::arithmetic(expr.operator(), &(*l).into(), &(*r).into())
+                }
+                result => result,
+            };
+            result.map_err(|kind| kind.at(expr.span()))
+        }
+        #[cfg(feature = "bigint")]
+        (Value::Integer(_) | Value::BigInteger(_), Value::Integer(_) | Value::BigInteger(_)) => {
+            bigint::arithmetic(expr.operator(), &bigint::widen(left), &bigint::widen(right))
+                .map_err(|kind| kind.at(expr.span()))
+        }
+        _ => {
+            let l = expect_number(left, expr.left().span())?;
+            let r = expect_number(right, expr.right().span())?;
+            Ok(Value::Number(float(l, r)))
+        }
+    }
 }
 
-fn less_equal(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-   Ok(LiteralExpression::boolean(binary_operation(expr, &|left, right| left <= right )?)) 
+fn overflow(result: Option<i64>) -> Result<i64, RuntimeErrorKind> {
+    result.ok_or(RuntimeErrorKind::IntegerOverflow)
 }
 
-fn equal(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-    let left = evaluate(expr.left().clone());
-    let right = evaluate(expr.right().clone());
-    Ok(LiteralExpression::boolean(left == right))
+/// Integer division and remainder have no answer for a zero divisor, unlike their float
+/// counterparts which give infinity or `NaN`.
+fn nonzero(divisor: i64) -> Result<i64, RuntimeErrorKind> {
+    match divisor {
+        0 => Err(RuntimeErrorKind::DivisionByZero),
+        x => Ok(x),
+    }
 }
 
-fn not_equal(expr: BinaryExpression) -> Result<LiteralExpression, RuntimeError> {
-    let left = evaluate(expr.left().clone());
-    let right = evaluate(expr.right().clone());
-    Ok(LiteralExpression::boolean(left != right))
+/// `span` locates the operand `value` was evaluated from.
+fn expect_number(value: &Value, span: Span) -> Result<f64, RuntimeError> {
+    value
+        .as_number()
+        .ok_or_else(|| RuntimeErrorKind::NumericOperandExpected(value.clone()).at(span))
 }
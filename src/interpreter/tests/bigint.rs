@@ -0,0 +1,75 @@
+use super::{evaluate, parse, run, run_error, RuntimeError, RuntimeErrorKind, Value};
+
+#[test]
+fn test_interpreter_bigint_promotes_on_overflow() -> Result<(), RuntimeError> {
+    let source = "\
+var max = 9223372036854775807;
+print max + 1;
+print -max - 2;
+print max * max;
+print -(-max - 1);
+print (-max - 1) ~/ -1;";
+    let expect = "\
+9223372036854775808
+-9223372036854775809
+85070591730234615847396907784232501249
+9223372036854775808
+9223372036854775808
+";
+    assert_eq!(run(source)?, expect);
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_bigint_literals() -> Result<(), RuntimeError> {
+    let source = "\
+print 123456789012345678901234567890;
+print 123456789012345678901234567890 + 1;
+print -9223372036854775808;";
+    let expect = "123456789012345678901234567890\n123456789012345678901234567891\n-9223372036854775808\n";
+    assert_eq!(run(source)?, expect);
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_bigint_shrinks_back() -> Result<(), RuntimeError> {
+    assert_eq!(evaluate(&parse("9223372036854775808 - 1"))?, Value::Integer(i64::MAX));
+    assert_eq!(evaluate(&parse("100000000000000000000 ~/ 10000000000"))?, Value::Integer(10_000_000_000));
+    assert_eq!(evaluate(&parse("100000000000000000007 % 10"))?, Value::Integer(7));
+    assert_eq!(evaluate(&parse("-100000000000000000007 % 10"))?, Value::Integer(-7));
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_bigint_comparison() -> Result<(), RuntimeError> {
+    let source = "\
+var big = 100000000000000000000;
+print big == 100000000000000000000;
+print big == big + 1;
+print big < big + 1;
+print big > 1;
+print -big < 1;
+print big == 100000000000000000000.0;
+print big + 0.5;";
+    assert_eq!(run(source)?, "true\nfalse\ntrue\ntrue\ntrue\ntrue\n100000000000000000000\n");
+
+    // 2^64 + 1 has no float of its own, but is still told apart from 2^64.
+    let table = [
+        ("18446744073709551617 == 18446744073709551616.0", false),
+        ("18446744073709551616 == 18446744073709551616.0", true),
+        ("18446744073709551617 > 18446744073709551616.0", true),
+        ("-18446744073709551617 < -18446744073709551616.0", true),
+        ("18446744073709551617 < 1/0", true),
+        ("18446744073709551617 == 0/0", false),
+    ];
+    for (expr, expect) in table {
+        assert_eq!(evaluate(&parse(expr))?, Value::Boolean(expect), "{expr}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_bigint_division_by_zero() {
+    assert_eq!(run_error("print 100000000000000000000 ~/ 0;"), RuntimeErrorKind::DivisionByZero);
+    assert_eq!(run_error("print 100000000000000000000 % 0;"), RuntimeErrorKind::DivisionByZero);
+}
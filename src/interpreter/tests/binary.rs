@@ -1,78 +1,111 @@
-use super::RuntimeError;
+use super::{Interpreter, RuntimeError, RuntimeErrorKind, Value};
 
-use super::{parse, assert_literal_number, evaluate, assert_literal_boolean};
+use super::{parse, assert_literal_number, assert_literal_integer, evaluate, assert_literal_boolean, run, run_error, run_in};
 
 #[test]
 fn test_interpreter_binary_add() -> Result<(), RuntimeError>{
-    assert_literal_number(evaluate(parse("42 + 27"))?, 69_f64);
-    assert_literal_number(evaluate(parse("42 + -42"))?, 0_f64);
+    assert_literal_integer(evaluate(&parse("42 + 27"))?, 69);
+    assert_literal_integer(evaluate(&parse("42 + -42"))?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_binary_add_strings() -> Result<(), RuntimeError> {
+    assert_eq!(evaluate(&parse("\"foo\" + \"bar\""))?, Value::String("foobar".into()));
+    assert_eq!(run("var a = \"a\";\nvar b = a + \"b\";\nprint a + b;")?, "aab\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_binary_add_mismatched_operands() {
+    assert_eq!(
+        run_error("print \"n = \" + 3;"),
+        RuntimeErrorKind::AddOperandsMismatch(Value::String("n = ".into()), Value::Integer(3))
+    );
+    assert!(matches!(run_error("print 1 + nil;"), RuntimeErrorKind::AddOperandsMismatch(..)));
+    assert!(matches!(run_error("print \"a\" + true;"), RuntimeErrorKind::AddOperandsMismatch(..)));
+
+    let err = run("print 1 +\n  \"a\";").unwrap_err();
+    assert_eq!((err.span.line, err.span.column), (1, 7), "Points at the whole addition");
+}
+
+#[test]
+fn test_interpreter_binary_add_string_coercion() -> Result<(), RuntimeError> {
+    let interpreter = || Interpreter::new(Vec::new()).with_string_coercion(true);
+    assert_eq!(run_in(interpreter(), "print \"n = \" + 3;")?, "n = 3\n");
+    assert_eq!(run_in(interpreter(), "print 2.5 + \"x\";")?, "2.5x\n");
+    assert_eq!(run_in(interpreter(), "print 1 + 2;")?, "3\n");
+    assert!(matches!(
+        run_in(interpreter(), "print \"a\" + nil;").unwrap_err().kind,
+        RuntimeErrorKind::AddOperandsMismatch(..)
+    ), "Only numbers are converted");
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_mult() -> Result<(), RuntimeError>{
-    assert_literal_number(evaluate(parse("0.5 * 10"))?, 5_f64);
+    assert_literal_number(evaluate(&parse("0.5 * 10"))?, 5_f64);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_sub() -> Result<(), RuntimeError> {
-    assert_literal_number(evaluate(parse("540 - 120"))?, 420_f64);
+    assert_literal_integer(evaluate(&parse("540 - 120"))?, 420);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_div_ok() -> Result<(), RuntimeError>{
-    assert_literal_number(evaluate(parse("20 / 2"))?, 10_f64);
+    assert_literal_number(evaluate(&parse("20 / 2"))?, 10_f64);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_greater() -> Result<(), RuntimeError>{
-    assert_literal_boolean(evaluate(parse("10 > 5"))?, true);
-    assert_literal_boolean(evaluate(parse("5 > 10"))?, false);
+    assert_literal_boolean(evaluate(&parse("10 > 5"))?, true);
+    assert_literal_boolean(evaluate(&parse("5 > 10"))?, false);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_greater_equal() -> Result<(), RuntimeError>{
-    assert_literal_boolean(evaluate(parse("10 >= 5"))?, true);
-    assert_literal_boolean(evaluate(parse("10 >= 10"))?, true);
-    assert_literal_boolean(evaluate(parse("5 >= 10"))?, false);
+    assert_literal_boolean(evaluate(&parse("10 >= 5"))?, true);
+    assert_literal_boolean(evaluate(&parse("10 >= 10"))?, true);
+    assert_literal_boolean(evaluate(&parse("5 >= 10"))?, false);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_less() -> Result<(), RuntimeError>{
-    assert_literal_boolean(evaluate(parse("10 < 5"))?, false);
-    assert_literal_boolean(evaluate(parse("5 < 10"))?, true);
+    assert_literal_boolean(evaluate(&parse("10 < 5"))?, false);
+    assert_literal_boolean(evaluate(&parse("5 < 10"))?, true);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_less_equal() -> Result<(), RuntimeError>{
-    assert_literal_boolean(evaluate(parse("10 <= 5"))?, false);
-    assert_literal_boolean(evaluate(parse("10 <= 10"))?, true);
-    assert_literal_boolean(evaluate(parse("5 <= 10"))?, true);
+    assert_literal_boolean(evaluate(&parse("10 <= 5"))?, false);
+    assert_literal_boolean(evaluate(&parse("10 <= 10"))?, true);
+    assert_literal_boolean(evaluate(&parse("5 <= 10"))?, true);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_is_equal() -> Result<(), RuntimeError>{
-    assert_literal_boolean(evaluate(parse("42 == 42"))?, true);
-    assert_literal_boolean(evaluate(parse("42 == 24"))?, false);
+    assert_literal_boolean(evaluate(&parse("42 == 42"))?, true);
+    assert_literal_boolean(evaluate(&parse("42 == 24"))?, false);
 
-    assert_literal_boolean(evaluate(parse("\"abc\" == \"abc\""))?, true);
-    assert_literal_boolean(evaluate(parse("\"abc\" == \"cba\""))?, false);
+    assert_literal_boolean(evaluate(&parse("\"abc\" == \"abc\""))?, true);
+    assert_literal_boolean(evaluate(&parse("\"abc\" == \"cba\""))?, false);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_binary_not_equal() -> Result<(), RuntimeError>{
-    assert_literal_boolean(evaluate(parse("42 != 42"))?, false);
-    assert_literal_boolean(evaluate(parse("42 != 24"))?, true);
+    assert_literal_boolean(evaluate(&parse("42 != 42"))?, false);
+    assert_literal_boolean(evaluate(&parse("42 != 24"))?, true);
 
-    assert_literal_boolean(evaluate(parse("\"abc\" != \"abc\""))?, false);
-    assert_literal_boolean(evaluate(parse("\"abc\" != \"cba\""))?, true);
+    assert_literal_boolean(evaluate(&parse("\"abc\" != \"abc\""))?, false);
+    assert_literal_boolean(evaluate(&parse("\"abc\" != \"cba\""))?, true);
     Ok(())
 }
@@ -0,0 +1,75 @@
+use super::{parse_program, run, run_error, Interpreter, RuntimeError, RuntimeErrorKind};
+
+#[test]
+fn test_interpreter_block_shadowing() -> Result<(), RuntimeError> {
+    let source = "
+        var a = \"global\";
+        {
+            var a = \"local\";
+            print a;
+        }
+        print a;
+    ";
+    assert_eq!(run(source)?, "local\nglobal\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_block_assign_enclosing() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a = 1; { a = 2; } print a;")?, "2\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_block_nested() -> Result<(), RuntimeError> {
+    let source = "
+        var a = \"global a\";
+        var b = \"global b\";
+        var c = \"global c\";
+        {
+            var a = \"outer a\";
+            var b = \"outer b\";
+            {
+                var a = \"inner a\";
+                print a;
+                print b;
+                print c;
+            }
+            print a;
+            print b;
+            print c;
+        }
+        print a;
+        print b;
+        print c;
+    ";
+    let expect = "inner a\nouter b\nglobal c\nouter a\nouter b\nglobal c\nglobal a\nglobal b\nglobal c\n";
+    assert_eq!(run(source)?, expect);
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_block_local_not_visible_outside() {
+    assert_eq!(
+        run_error("{ var a = 1; } print a;"),
+        RuntimeErrorKind::UndefinedVariable { name: "a".to_string(), suggestion: None }
+    );
+}
+
+#[test]
+fn test_interpreter_block_redeclaration() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a = 1; var a = 2; print a;")?, "2\n");
+    assert_eq!(run("{ var a = 1; { var a = 2; print a; } }")?, "2\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_block_restores_scope_after_error() -> Result<(), RuntimeError> {
+    let mut interpreter = Interpreter::new(Vec::new());
+    let statements = parse_program("var a = \"global\"; { var a = \"local\"; -a; }");
+    assert!(interpreter.interpret(&statements).is_err());
+
+    interpreter.interpret(&parse_program("print a;"))?;
+    assert_eq!(String::from_utf8(interpreter.out).unwrap(), "global\n");
+    Ok(())
+}
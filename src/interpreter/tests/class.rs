@@ -0,0 +1,126 @@
+use super::{run, run_error, RuntimeError, RuntimeErrorKind};
+
+#[test]
+fn test_interpreter_class_and_instance_print() -> Result<(), RuntimeError> {
+    assert_eq!(run("class Bagel {} print Bagel; print Bagel();")?, "Bagel\nBagel instance\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_fields() -> Result<(), RuntimeError> {
+    let source = "
+        class Point {}
+        var p = Point();
+        p.x = 1;
+        p.y = p.x + 1;
+        print p.x;
+        print p.y;
+    ";
+    assert_eq!(run(source)?, "1\n2\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_methods_bind_this() -> Result<(), RuntimeError> {
+    let source = "
+        class Person {
+            greet() { print \"Hi, \" ; print this.name; }
+        }
+        var p = Person();
+        p.name = \"Ada\";
+        var greet = p.greet;
+        greet();
+    ";
+    assert_eq!(run(source)?, "Hi, \nAda\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_this_in_closure() -> Result<(), RuntimeError> {
+    let source = "
+        class Thing {
+            callback() {
+                fun local() { print this.label; }
+                return local;
+            }
+        }
+        var thing = Thing();
+        thing.label = \"thing\";
+        thing.callback()();
+    ";
+    assert_eq!(run(source)?, "thing\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_initializer() -> Result<(), RuntimeError> {
+    let source = "
+        class Counter {
+            init(start) { this.count = start; }
+            increment() { this.count = this.count + 1; return this; }
+        }
+        var c = Counter(5);
+        print c.increment().increment().count;
+    ";
+    assert_eq!(run(source)?, "7\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_initializer_returns_instance() -> Result<(), RuntimeError> {
+    let source = "
+        class Foo {
+            init() { this.ready = true; return; }
+        }
+        var foo = Foo();
+        print foo.init();
+        print foo.init() == foo;
+    ";
+    assert_eq!(run(source)?, "Foo instance\ntrue\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_fields_shadow_methods() -> Result<(), RuntimeError> {
+    let source = "
+        class A { m() { return \"method\"; } }
+        var a = A();
+        fun f() { return \"field\"; }
+        a.m = f;
+        print a.m();
+    ";
+    assert_eq!(run(source)?, "field\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_class_initializer_arity() {
+    assert_eq!(
+        run_error("class P { init(x, y) {} } P(1);"),
+        RuntimeErrorKind::ArityMismatch {
+            name: "P".to_string(),
+            expected: 2,
+            found: 1,
+        }
+    );
+}
+
+#[test]
+fn test_interpreter_class_undefined_property() {
+    assert_eq!(
+        run_error("class A {} A().missing;"),
+        RuntimeErrorKind::UndefinedProperty { name: "missing".to_string(), suggestion: None }
+    );
+}
+
+#[test]
+fn test_interpreter_class_property_on_non_instance() {
+    assert!(matches!(
+        run_error("var x = 1; x.y;"),
+        RuntimeErrorKind::OnlyInstancesHaveProperties(_)
+    ));
+    assert!(matches!(
+        run_error("\"str\".y = 2;"),
+        RuntimeErrorKind::OnlyInstancesHaveProperties(_)
+    ));
+}
@@ -0,0 +1,104 @@
+use super::{run, RuntimeError};
+
+#[test]
+fn test_interpreter_closure_counter() -> Result<(), RuntimeError> {
+    let source = "
+        fun make_counter() {
+            var i = 0;
+            fun count() {
+                i = i + 1;
+                return i;
+            }
+            return count;
+        }
+        var counter = make_counter();
+        print counter();
+        print counter();
+        var other = make_counter();
+        print other();
+    ";
+    assert_eq!(run(source)?, "1\n2\n1\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_closure_shares_captured_variable() -> Result<(), RuntimeError> {
+    let source = "
+        var get;
+        var set;
+        fun make() {
+            var value = \"initial\";
+            fun getter() { return value; }
+            fun setter(x) { value = x; }
+            get = getter;
+            set = setter;
+        }
+        make();
+        print get();
+        set(\"updated\");
+        print get();
+    ";
+    assert_eq!(run(source)?, "initial\nupdated\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_closure_captures_block_scope() -> Result<(), RuntimeError> {
+    let source = "
+        var show;
+        {
+            var message = \"from block\";
+            fun f() { print message; }
+            show = f;
+        }
+        show();
+    ";
+    assert_eq!(run(source)?, "from block\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_closure_local_recursion() -> Result<(), RuntimeError> {
+    let source = "
+        fun outer() {
+            fun countdown(n) {
+                if (n < 0) return;
+                print n;
+                countdown(n - 1);
+            }
+            countdown(2);
+        }
+        outer();
+    ";
+    assert_eq!(run(source)?, "2\n1\n0\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_closure_callback() -> Result<(), RuntimeError> {
+    let source = "
+        fun twice(f, x) { return f(f(x)); }
+        fun make_adder(n) {
+            fun add(x) { return x + n; }
+            return add;
+        }
+        print twice(make_adder(10), 1);
+    ";
+    assert_eq!(run(source)?, "21\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_closure_binds_statically() -> Result<(), RuntimeError> {
+    let source = "
+        var a = \"global\";
+        {
+            fun show() { print a; }
+            show();
+            var a = \"block\";
+            show();
+        }
+    ";
+    assert_eq!(run(source)?, "global\nglobal\n");
+    Ok(())
+}
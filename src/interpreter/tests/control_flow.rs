@@ -0,0 +1,133 @@
+use super::{run, run_error, RuntimeError, RuntimeErrorKind};
+
+#[test]
+fn test_interpreter_if_else() -> Result<(), RuntimeError> {
+    assert_eq!(run("if (true) print 1; else print 2;")?, "1\n");
+    assert_eq!(run("if (false) print 1; else print 2;")?, "2\n");
+    assert_eq!(run("if (nil) print 1;")?, "");
+    assert_eq!(run("if (0) print \"zero is truthy\";")?, "zero is truthy\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_if_dangling_else() -> Result<(), RuntimeError> {
+    assert_eq!(run("if (true) if (false) print 1; else print 2;")?, "2\n");
+    assert_eq!(run("if (false) if (true) print 1; else print 2;")?, "");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_logical_returns_deciding_operand() -> Result<(), RuntimeError> {
+    assert_eq!(run("print \"hi\" or 2;")?, "hi\n");
+    assert_eq!(run("print nil or \"yes\";")?, "yes\n");
+    assert_eq!(run("print nil and 1;")?, "nil\n");
+    assert_eq!(run("print 1 and \"last\";")?, "last\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_logical_short_circuits() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a = 1; true or (a = 2); print a;")?, "1\n");
+    assert_eq!(run("var a = 1; false and (a = 2); print a;")?, "1\n");
+    assert_eq!(run("var a = 1; false or (a = 2); print a;")?, "2\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_while() -> Result<(), RuntimeError> {
+    assert_eq!(run("var i = 0; while (i < 3) { print i; i = i + 1; }")?, "0\n1\n2\n");
+    assert_eq!(run("while (false) print 1;")?, "");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_for() -> Result<(), RuntimeError> {
+    assert_eq!(run("for (var i = 0; i < 3; i = i + 1) print i;")?, "0\n1\n2\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_for_fibonacci() -> Result<(), RuntimeError> {
+    let source = "
+        var a = 0;
+        var temp;
+        for (var b = 1; a < 50; b = temp + b) {
+            print a;
+            temp = a;
+            a = b;
+        }
+    ";
+    assert_eq!(run(source)?, "0\n1\n1\n2\n3\n5\n8\n13\n21\n34\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_for_optional_clauses() -> Result<(), RuntimeError> {
+    assert_eq!(run("var i = 0; for (; i < 2;) { print i; i = i + 1; }")?, "0\n1\n");
+    assert_eq!(run("var i = 0; for (i = 5; i < 7; i = i + 1) print i;")?, "5\n6\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_for_initializer_is_scoped() {
+    assert_eq!(
+        run_error("for (var i = 0; i < 1; i = i + 1) {} print i;"),
+        RuntimeErrorKind::UndefinedVariable { name: "i".to_string(), suggestion: None }
+    );
+}
+
+#[test]
+fn test_interpreter_break() -> Result<(), RuntimeError> {
+    assert_eq!(run("var i = 0; while (true) { if (i == 2) break; print i; i = i + 1; }")?, "0\n1\n");
+    assert_eq!(run("for (var i = 0; i < 5; i = i + 1) { if (i == 1) break; print i; }")?, "0\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_continue_runs_for_increment() -> Result<(), RuntimeError> {
+    let source = "for (var i = 0; i < 4; i = i + 1) { if (i == 1) continue; print i; }";
+    assert_eq!(run(source)?, "0\n2\n3\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_continue_in_while() -> Result<(), RuntimeError> {
+    let source = "var i = 0; while (i < 3) { i = i + 1; if (i == 2) continue; print i; }";
+    assert_eq!(run(source)?, "1\n3\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_break_only_innermost_loop() -> Result<(), RuntimeError> {
+    let source = "
+        for (var i = 0; i < 2; i = i + 1) {
+            for (var j = 0; j < 5; j = j + 1) {
+                if (j == 1) break;
+                print i + j;
+            }
+        }
+    ";
+    assert_eq!(run(source)?, "0\n1\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_labeled_break_and_continue() -> Result<(), RuntimeError> {
+    let source = "
+        outer: for (var i = 0; i < 3; i = i + 1) {
+            for (var j = 0; j < 3; j = j + 1) {
+                if (j == 1) continue outer;
+                if (i == 2) break outer;
+                print i * 10 + j;
+            }
+        }
+    ";
+    assert_eq!(run(source)?, "0\n10\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_break_restores_scope() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a = \"outer\"; while (true) { var a = \"inner\"; break; } print a;")?, "outer\n");
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use super::{run, run_error, RuntimeError, RuntimeErrorKind};
+
+/// One value of every kind, as a Lox expression.
+const VALUES: [&str; 8] = ["nil", "true", "1", "1.5", "\"1\"", "f", "A", "a"];
+const PRELUDE: &str = "fun f() {}\nclass A {}\nvar a = A();\n";
+
+#[test]
+fn test_interpreter_equality_across_kinds() -> Result<(), RuntimeError> {
+    for (i, left) in VALUES.iter().enumerate() {
+        for (j, right) in VALUES.iter().enumerate() {
+            let source = format!("{PRELUDE}print {left} == {right};\nprint {left} != {right};");
+            let expect = if i == j { "true\nfalse\n" } else { "false\ntrue\n" };
+            assert_eq!(run(&source)?, expect, "{left} == {right}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_equality_within_kinds() -> Result<(), RuntimeError> {
+    let table = [
+        ("true == false", false),
+        ("false == false", true),
+        ("1 == 2", false),
+        ("0 == -0", true),
+        ("\"a\" == \"b\"", false),
+        ("\"a\" + \"b\" == \"ab\"", true),
+        ("f == g", false),
+        ("A == B", false),
+        ("a == A()", false),
+        ("a == b", true),
+        ("0/0 == 0/0", false),
+        ("nan == nan", false),
+        ("nan != nan", true),
+    ];
+    let prelude = format!("{PRELUDE}fun g() {{}}\nclass B {{}}\nvar b = a;\nvar nan = 0/0;\n");
+    for (expr, expect) in table {
+        assert_eq!(run(&format!("{prelude}print {expr};"))?, format!("{expect}\n"), "{expr}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_equality_propagates_errors() {
+    for source in ["print -nil == 1;", "print 1 == -nil;", "print -nil != 1;", "print 1 != -nil;"] {
+        assert!(
+            matches!(run_error(source), RuntimeErrorKind::NumericOperandExpected(_)),
+            "{source}"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use super::{run, run_error, RuntimeError, RuntimeErrorKind};
+use crate::interpreter::{MAX_CALL_DEPTH, STACK_SIZE};
+
+#[test]
+fn test_interpreter_function_call() -> Result<(), RuntimeError> {
+    assert_eq!(run("fun greet() { print \"hi\"; } greet();")?, "hi\n");
+    assert_eq!(run("fun add(a, b) { print a + b; } add(1, 2);")?, "3\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_function_return() -> Result<(), RuntimeError> {
+    assert_eq!(run("fun add(a, b) { return a + b; } print add(1, 2);")?, "3\n");
+    assert_eq!(run("fun f() { return; } print f();")?, "nil\n");
+    assert_eq!(run("fun f() {} print f();")?, "nil\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_function_return_exits_loops() -> Result<(), RuntimeError> {
+    let source = "
+        fun first_over(limit) {
+            for (var i = 0; ; i = i + 1) {
+                while (true) {
+                    if (i > limit) return i;
+                    break;
+                }
+            }
+        }
+        print first_over(3);
+    ";
+    assert_eq!(run(source)?, "4\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_function_recursion() -> Result<(), RuntimeError> {
+    let source = "
+        fun fib(n) {
+            if (n <= 1) return n;
+            return fib(n - 2) + fib(n - 1);
+        }
+        print fib(10);
+    ";
+    assert_eq!(run(source)?, "55\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_function_is_value() -> Result<(), RuntimeError> {
+    assert_eq!(run("fun f() {} print f;")?, "<fn f>\n");
+    assert_eq!(run("fun f() { return 1; } var g = f; print g();")?, "1\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_function_parameters_are_local() {
+    assert_eq!(
+        run_error("fun f(a) {} f(1); print a;"),
+        RuntimeErrorKind::UndefinedVariable { name: "a".to_string(), suggestion: None }
+    );
+}
+
+#[test]
+fn test_interpreter_function_arity_mismatch() {
+    assert_eq!(
+        run_error("fun add(a, b) {} add(1);"),
+        RuntimeErrorKind::ArityMismatch {
+            name: "add".to_string(),
+            expected: 2,
+            found: 1,
+        }
+    );
+}
+
+#[test]
+fn test_interpreter_function_not_callable() {
+    assert!(matches!(
+        run_error("\"not a function\"();"),
+        RuntimeErrorKind::NotCallable(_)
+    ));
+}
+
+#[test]
+fn test_interpreter_function_stack_overflow() {
+    // Reaching the limit takes more stack than a test thread gets.
+    let test = || {
+        let source = "fun c(n) { if (n == 0) return 0; return c(n - 1) + 1; }\nprint c(500);";
+        assert_eq!(run(source).unwrap(), "500\n");
+
+        let source = "fun c(n) { if (n == 0) return 0; return c(n - 1) + 1; }\nprint c(5000);";
+        let err = run(source).unwrap_err();
+        assert_eq!(err.kind, RuntimeErrorKind::StackOverflow);
+        assert_eq!(err.trace.len(), MAX_CALL_DEPTH, "Every running call is in the trace");
+
+        assert_eq!(run_error("fun f() { f(); }\nf();"), RuntimeErrorKind::StackOverflow);
+    };
+    std::thread::Builder::new().stack_size(STACK_SIZE).spawn(test).unwrap().join().unwrap();
+}
@@ -0,0 +1,100 @@
+use super::{run, run_error, RuntimeError, RuntimeErrorKind, Value};
+
+#[test]
+fn test_interpreter_inherited_method() -> Result<(), RuntimeError> {
+    let source = "
+        class Doughnut {
+            cook() { print \"Fry until golden brown.\"; }
+        }
+        class BostonCream < Doughnut {}
+        BostonCream().cook();
+    ";
+    assert_eq!(run(source)?, "Fry until golden brown.\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_super_call() -> Result<(), RuntimeError> {
+    let source = "
+        class Doughnut {
+            cook() { print \"Fry until golden brown.\"; }
+        }
+        class BostonCream < Doughnut {
+            cook() {
+                super.cook();
+                print \"Pipe full of custard.\";
+            }
+        }
+        BostonCream().cook();
+    ";
+    assert_eq!(run(source)?, "Fry until golden brown.\nPipe full of custard.\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_super_binds_this() -> Result<(), RuntimeError> {
+    let source = "
+        class A {
+            method() { print \"A method\"; }
+            test() { this.method(); }
+        }
+        class B < A {
+            method() { print \"B method\"; }
+            test() { super.test(); }
+        }
+        class C < B {}
+        C().test();
+    ";
+    assert_eq!(run(source)?, "B method\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_super_resolves_statically() -> Result<(), RuntimeError> {
+    let source = "
+        class A {
+            method() { print \"A method\"; }
+        }
+        class B < A {
+            method() { print \"B method\"; }
+            test() { super.method(); }
+        }
+        class C < B {}
+        C().test();
+    ";
+    assert_eq!(run(source)?, "A method\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_inherited_initializer() -> Result<(), RuntimeError> {
+    let source = "
+        class A {
+            init(value) { this.value = value; }
+        }
+        class B < A {}
+        print B(3).value;
+    ";
+    assert_eq!(run(source)?, "3\n");
+    assert!(matches!(
+        run_error("class A { init(x) {} } class B < A {} B();"),
+        RuntimeErrorKind::ArityMismatch { expected: 1, found: 0, .. }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_superclass_must_be_class() {
+    assert_eq!(
+        run_error("var NotAClass = 1; class A < NotAClass {}"),
+        RuntimeErrorKind::SuperclassMustBeClass(Value::Integer(1))
+    );
+}
+
+#[test]
+fn test_interpreter_super_undefined_method() {
+    assert_eq!(
+        run_error("class A {} class B < A { m() { super.missing(); } } B().m();"),
+        RuntimeErrorKind::UndefinedProperty { name: "missing".to_string(), suggestion: None }
+    );
+}
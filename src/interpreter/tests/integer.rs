@@ -0,0 +1,91 @@
+use super::{evaluate, parse, run, run_error, RuntimeError, RuntimeErrorKind, Value};
+
+#[test]
+fn test_interpreter_integer_literals() -> Result<(), RuntimeError> {
+    assert_eq!(evaluate(&parse("7"))?, Value::Integer(7));
+    assert_eq!(evaluate(&parse("7.0"))?, Value::Number(7_f64));
+    assert_eq!(evaluate(&parse("-9223372036854775807"))?, Value::Integer(-i64::MAX));
+    assert_eq!(evaluate(&parse("-9223372036854775808"))?, Value::Integer(i64::MIN));
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_integer_arithmetic() -> Result<(), RuntimeError> {
+    assert_eq!(evaluate(&parse("2 + 3 * 4 - 1"))?, Value::Integer(13));
+    assert_eq!(evaluate(&parse("7 ~/ 2"))?, Value::Integer(3));
+    assert_eq!(evaluate(&parse("-7 ~/ 2"))?, Value::Integer(-3), "Rounds towards zero");
+    assert_eq!(evaluate(&parse("7 % 3"))?, Value::Integer(1));
+    assert_eq!(evaluate(&parse("-7 % 3"))?, Value::Integer(-1), "Takes the sign of the dividend");
+    assert_eq!(evaluate(&parse("7 / 2"))?, Value::Number(3.5), "'/' always divides as floats");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_integer_promotion() -> Result<(), RuntimeError> {
+    assert_eq!(evaluate(&parse("1 + 0.5"))?, Value::Number(1.5));
+    assert_eq!(evaluate(&parse("0.5 * 4"))?, Value::Number(2_f64));
+    assert_eq!(evaluate(&parse("7.5 ~/ 2"))?, Value::Number(3_f64));
+    assert_eq!(evaluate(&parse("7.5 % 2"))?, Value::Number(1.5));
+    assert_eq!(evaluate(&parse("1 == 1.0"))?, Value::Boolean(true));
+    assert_eq!(evaluate(&parse("1 < 1.5"))?, Value::Boolean(true));
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_integer_precision() -> Result<(), RuntimeError> {
+    // 2^53 + 1 can't be represented as a float.
+    let source = "\
+var id = 9007199254740993;
+print id;
+print id + 1;
+print id == id - 1;
+print id > id - 1;";
+    assert_eq!(run(source)?, "9007199254740993\n9007199254740994\nfalse\ntrue\n");
+
+    // Integers and floats are compared exactly too, rather than rounding the integer.
+    let table = [
+        ("9007199254740993 == 9007199254740992.0", false),
+        ("9007199254740992 == 9007199254740992.0", true),
+        ("9007199254740993 > 9007199254740992.0", true),
+        ("9007199254740992.0 < 9007199254740993", true),
+        ("9007199254740993 <= 9007199254740992.0", false),
+        ("9223372036854775807 < 9223372036854775808.0", true),
+        ("-9223372036854775807 > -9223372036854775808.0", true),
+        ("-1 > -1.5", true),
+        ("1 < 1.5", true),
+        ("1 == 0/0", false),
+        ("1 < 1/0", true),
+        ("1 >= 0/0", false),
+    ];
+    for (expr, expect) in table {
+        assert_eq!(evaluate(&parse(expr))?, Value::Boolean(expect), "{expr}");
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "bigint"))]
+fn test_interpreter_integer_overflow() {
+    for source in [
+        "print 9223372036854775807 + 1;",
+        "print -9223372036854775807 - 2;",
+        "print 4611686018427387904 * 2;",
+        "print -(-9223372036854775807 - 1);",
+        "print (-9223372036854775807 - 1) ~/ -1;",
+    ] {
+        assert_eq!(run_error(source), RuntimeErrorKind::IntegerOverflow, "{source}");
+    }
+    let min = "(-9223372036854775807 - 1)";
+    assert_eq!(run(&format!("print {min} % -1;")).unwrap(), "0\n", "The remainder fits even if the quotient doesn't");
+
+    let err = run("var max = 9223372036854775807;\nprint 1 + max + 1;").unwrap_err();
+    assert_eq!((err.span.line, err.span.column), (2, 7), "Points at the overflowing operation");
+}
+
+#[test]
+fn test_interpreter_integer_division_by_zero() -> Result<(), RuntimeError> {
+    assert_eq!(run_error("print 1 ~/ 0;"), RuntimeErrorKind::DivisionByZero);
+    assert_eq!(run_error("print 1 % 0;"), RuntimeErrorKind::DivisionByZero);
+    assert_eq!(run("print 1 / 0;\nprint 1.0 ~/ 0;\nprint 1 % 0.0;")?, "Infinity\nInfinity\nNaN\n");
+    Ok(())
+}
@@ -1,46 +1,94 @@
-use super::{evaluate, RuntimeError};
-use crate::{lexer, parser, expression::{self, LiteralExpression}};
+use super::{Interpreter, RuntimeError, RuntimeErrorKind, Value};
+use crate::{lexer, parser, resolver, expression, statement::Stmt};
 
 fn parse(source: &str) -> expression::Expr {
+    let source = format!("{source};");
     let mut tokens = lexer::Lexer::from_iter(source.chars()).peekable();
-    parser::parse(&mut tokens).unwrap()
+    match parser::parse(&mut tokens).unwrap().pop() {
+        Some(Stmt::Expression(expr)) => expr,
+        stmt => panic!("Expected an expression statement, found {stmt:?}"),
+    }
 }
 
-fn assert_literal_number(result: expression::Expr, expect: f64) {
-    assert_eq!(result, expression::Expr::Literal(LiteralExpression::number(expect)));
+fn evaluate(expr: &expression::Expr) -> Result<Value, RuntimeError> {
+    Interpreter::new(Vec::new()).evaluate(expr)
 }
 
-fn assert_literal_boolean(result: expression::Expr, expect: bool) {
-    assert_eq!(result, expression::Expr::Literal(LiteralExpression::boolean(expect)));
+fn parse_program(source: &str) -> Vec<Stmt> {
+    let mut tokens = lexer::Lexer::from_iter(source.chars()).peekable();
+    let statements = parser::parse(&mut tokens).unwrap();
+    resolver::resolve(&statements).unwrap();
+    statements
+}
+
+fn run(source: &str) -> Result<String, RuntimeError> {
+    run_in(Interpreter::new(Vec::new()), source)
+}
+
+/// Runs a program in an interpreter set up by the test and returns what it printed.
+fn run_in(mut interpreter: Interpreter<Vec<u8>>, source: &str) -> Result<String, RuntimeError> {
+    let statements = parse_program(source);
+    interpreter.interpret(&statements)?;
+    Ok(String::from_utf8(interpreter.out).unwrap())
+}
+
+/// Runs a program that is expected to fail and returns what went wrong.
+fn run_error(source: &str) -> RuntimeErrorKind {
+    run(source).expect_err("Expected a runtime error").kind
+}
+
+fn assert_literal_number(result: Value, expect: f64) {
+    assert_eq!(result, Value::Number(expect));
+}
+
+fn assert_literal_integer(result: Value, expect: i64) {
+    assert_eq!(result, Value::Integer(expect));
+}
+
+fn assert_literal_boolean(result: Value, expect: bool) {
+    assert_eq!(result, Value::Boolean(expect));
 }
 
 #[test]
 fn test_interpreter_group() -> Result<(), RuntimeError>{
     let expr = parse("(123)");
-    let result = evaluate(expr)?;
-    let expect = expression::Expr::Literal(LiteralExpression::number(123_f64));
+    let result = evaluate(&expr)?;
+    let expect = Value::Integer(123);
     assert_eq!(result, expect);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_expression_add_mult() -> Result<(), RuntimeError>{
-    assert_literal_number(evaluate(parse("2 * 10 + 5"))?, 25_f64);
+    assert_literal_integer(evaluate(&parse("2 * 10 + 5"))?, 25);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_expression_add_group() -> Result<(), RuntimeError>{
-    assert_literal_number(evaluate(parse("(10 + 5)"))?, 15_f64);
+    assert_literal_integer(evaluate(&parse("(10 + 5)"))?, 15);
     Ok(())
 }
 
 #[test]
 fn test_interpreter_expression_mult_group_add() -> Result<(), RuntimeError>{
-    assert_literal_number(evaluate(parse("2 * (10 + 5)"))?, 30_f64);
+    assert_literal_integer(evaluate(&parse("2 * (10 + 5)"))?, 30);
     Ok(())
 }
 
 mod binary;
 mod unary;
 mod runtime_error;
+mod statement;
+mod variable;
+mod block;
+mod control_flow;
+mod function;
+mod closure;
+mod class;
+mod inheritance;
+mod value;
+mod equality;
+mod integer;
+#[cfg(feature = "bigint")]
+mod bigint;
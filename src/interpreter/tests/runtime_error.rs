@@ -1,9 +1,66 @@
-use super::{parse, evaluate};
+use super::{parse, evaluate, run, RuntimeErrorKind};
 
 #[test]
 fn test_interpreter_runtime_error_number_expected() -> Result<(), String>{
-    match evaluate(parse("2 + false")) {
+    match evaluate(&parse("2 + false")) {
         Ok(_) => panic!("Expected runtime error"),
         Err(_) => Ok(()),
     }
 }
+
+#[test]
+fn test_interpreter_runtime_error_span() {
+    let err = run("var a = 1;\nprint a - false;").unwrap_err();
+    assert!(matches!(err.kind, RuntimeErrorKind::NumericOperandExpected(_)));
+    assert_eq!((err.span.line, err.span.column), (2, 11), "Points at the offending operand");
+
+    let err = run("fun f(a) {}\n  f();").unwrap_err();
+    assert!(matches!(err.kind, RuntimeErrorKind::ArityMismatch { .. }));
+    assert_eq!((err.span.start, err.span.end, err.span.line, err.span.column), (14, 17, 2, 3));
+}
+
+#[test]
+fn test_interpreter_runtime_error_trace() {
+    let source = "\
+fun inner(n) {
+  return n + nil;
+}
+fun outer() {
+  return inner(1);
+}
+outer();";
+    let err = run(source).unwrap_err();
+    let stack: Vec<_> = err.stack().map(|(function, span)| (function, span.line)).collect();
+    assert_eq!(stack, vec![(Some("inner"), 2), (Some("outer"), 5), (None, 7)]);
+}
+
+#[test]
+fn test_interpreter_runtime_error_trace_through_methods() {
+    let source = "\
+class A {
+  init() { this.x = -\"a\"; }
+}
+A();";
+    let err = run(source).unwrap_err();
+    let stack: Vec<_> = err.stack().map(|(function, span)| (function, span.line)).collect();
+    assert_eq!(stack, vec![(Some("A"), 2), (None, 4)]);
+}
+
+#[test]
+fn test_interpreter_runtime_error_suggests_names() {
+    let help = |source: &str| run(source).unwrap_err().help();
+    let did_you_mean = |name: &str| Some(format!("did you mean '{name}'?"));
+
+    assert_eq!(help("var count = 1;\nprint cont;"), did_you_mean("count"));
+    assert_eq!(help("var count = 1;\n{ var total = 2; cout = total; }"), did_you_mean("count"));
+    assert_eq!(help("fun f(total) { return totl; }\nf(1);"), did_you_mean("total"));
+    assert_eq!(help("print nothing;"), None);
+
+    let class = "class A { speak() {} }\nclass B < A { init() { this.volume = 1; } }\n";
+    assert_eq!(help(&format!("{class}B().speek();")), did_you_mean("speak"));
+    assert_eq!(help(&format!("{class}print B().volme;")), did_you_mean("volume"));
+    assert_eq!(
+        help("class A { speak() {} }\nclass B < A { shout() { super.speek(); } }\nB().shout();"),
+        did_you_mean("speak")
+    );
+}
@@ -0,0 +1,19 @@
+use super::{run, RuntimeError};
+
+#[test]
+fn test_interpreter_statement_print() -> Result<(), RuntimeError> {
+    assert_eq!(run("print \"Hello, world!\";")?, "Hello, world!\n");
+    assert_eq!(run("print 1 + 2;")?, "3\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_statement_program() -> Result<(), RuntimeError> {
+    assert_eq!(run("print true; 1 + 1; print nil;")?, "true\nnil\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_statement_runtime_error_stops_execution() {
+    assert!(run("print 1; -\"a\"; print 2;").is_err());
+}
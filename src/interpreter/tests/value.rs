@@ -0,0 +1,43 @@
+use super::{run, RuntimeError, Value};
+
+#[test]
+fn test_interpreter_value_display() -> Result<(), RuntimeError> {
+    let source = "\
+print nil;
+print true;
+print 3;
+print 2.5;
+print \"text\";
+fun f() {}
+print f;
+class A {}
+print A;
+print A();";
+    assert_eq!(run(source)?, "nil\ntrue\n3\n2.5\ntext\n<fn f>\nA\nA instance\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_value_display_numbers() -> Result<(), RuntimeError> {
+    let source = "\
+print 3.0;
+print -0.0;
+print -0;
+print 0.1 + 0.2;
+print 1000000;
+print 1/0;
+print -1/0;
+print 0/0;";
+    let expect = "3\n-0\n0\n0.30000000000000004\n1000000\nInfinity\n-Infinity\nNaN\n";
+    assert_eq!(run(source)?, expect);
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_value_truthiness() {
+    assert!(!Value::Nil.is_truthy());
+    assert!(!Value::Boolean(false).is_truthy());
+    assert!(Value::Boolean(true).is_truthy());
+    assert!(Value::Number(0_f64).is_truthy(), "Zero is truthy in Lox");
+    assert!(Value::String("".into()).is_truthy());
+}
@@ -0,0 +1,34 @@
+use super::{run, run_error, RuntimeError, RuntimeErrorKind};
+
+#[test]
+fn test_interpreter_variable_declaration() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a = 1; print a;")?, "1\n");
+    assert_eq!(run("var a; print a;")?, "nil\n");
+    assert_eq!(run("var a = 1; var a = \"again\"; print a;")?, "again\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_variable_assignment() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a = 1; a = a + 1; print a;")?, "2\n");
+    assert_eq!(run("var a; print a = 3;")?, "3\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_variable_assignment_chained() -> Result<(), RuntimeError> {
+    assert_eq!(run("var a; var b; a = b = 3; print a; print b;")?, "3\n3\n");
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_variable_undefined() {
+    assert_eq!(
+        run_error("print x;"),
+        RuntimeErrorKind::UndefinedVariable { name: "x".to_string(), suggestion: None }
+    );
+    assert_eq!(
+        run_error("x = 1;"),
+        RuntimeErrorKind::UndefinedVariable { name: "x".to_string(), suggestion: None }
+    );
+}
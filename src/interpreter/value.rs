@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use super::{Class, Function, Instance};
+use crate::expression::LiteralOperator;
+
+/// Anything a Lox expression can evaluate to.
+///
+/// Cloning is cheap: strings, functions, classes and instances are shared rather than copied.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+    Nil,
+    Boolean(bool),
+    Number(f64),
+    Integer(i64),
+    /// An integer that doesn't fit in `Integer`. Arithmetic on integers moves between the two
+    /// as needed.
+    #[cfg(feature = "bigint")]
+    BigInteger(Rc<num_bigint::BigInt>),
+    String(Rc<str>),
+    Callable(Rc<Function>),
+    Class(Rc<Class>),
+    Instance(Rc<Instance>),
+}
+
+impl Value {
+    /// `nil` and `false` are falsey, everything else is truthy.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Self::Nil | Self::Boolean(false))
+    }
+
+    /// Equality as Lox defines it: nothing is converted, so values of different kinds are
+    /// never equal. Integers and floats are both numbers, and are equal when their values are
+    /// exactly the same. Floats follow IEEE 754, so `NaN` isn't even equal to itself, and
+    /// functions, classes and instances are only equal to themselves.
+    pub fn is_equal(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Self::Nil, Self::Nil) => true,
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Callable(a), Self::Callable(b)) => Rc::ptr_eq(a, b),
+            (Self::Class(a), Self::Class(b)) => Rc::ptr_eq(a, b),
+            (Self::Instance(a), Self::Instance(b)) => Rc::ptr_eq(a, b),
+            _ => self.compare(other) == Some(Ordering::Equal),
+        }
+    }
+
+    /// Orders two numbers by their exact values, so that an integer is never rounded to the
+    /// nearest float to compare it with one. `None` if either isn't a number, or is `NaN`.
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => a.partial_cmp(b),
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            (Self::Integer(a), Self::Number(b)) => compare_integer_float(*a, *b),
+            (Self::Number(a), Self::Integer(b)) => compare_integer_float(*b, *a).map(Ordering::reverse),
+            #[cfg(feature = "bigint")]
+            (Self::Integer(_) | Self::BigInteger(_), Self::Integer(_) | Self::BigInteger(_)) => {
+                Some(super::bigint::widen(self).cmp(&super::bigint::widen(other)))
+            }
+            #[cfg(feature = "bigint")]
+            (Self::BigInteger(a), Self::Number(b)) => super::bigint::compare_float(a, *b),
+            #[cfg(feature = "bigint")]
+            (Self::Number(a), Self::BigInteger(b)) => {
+                super::bigint::compare_float(b, *a).map(Ordering::reverse)
+            }
+            _ => None,
+        }
+    }
+
+    /// The value as a float, converting an integer if need be.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(x) => Some(*x),
+            Self::Integer(x) => Some(*x as f64),
+            #[cfg(feature = "bigint")]
+            Self::BigInteger(x) => num_traits::ToPrimitive::to_f64(x.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<&LiteralOperator> for Value {
+    fn from(literal: &LiteralOperator) -> Self {
+        match literal {
+            LiteralOperator::Nil => Self::Nil,
+            LiteralOperator::Boolean(x) => Self::Boolean(*x),
+            LiteralOperator::Number(x) => Self::Number(*x),
+            LiteralOperator::Integer(x) => Self::Integer(*x),
+            #[cfg(feature = "bigint")]
+            LiteralOperator::BigInteger(x) => super::bigint::normalize(x.clone()),
+            LiteralOperator::String(x) => Self::String(Rc::from(x.as_str())),
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Nil => write!(f, "nil"),
+            Self::Boolean(x) => write!(f, "{x}"),
+            Self::Number(x) => write!(f, "{}", format_number(*x)),
+            Self::Integer(x) => write!(f, "{x}"),
+            #[cfg(feature = "bigint")]
+            Self::BigInteger(x) => write!(f, "{x}"),
+            Self::String(x) => write!(f, "{x}"),
+            Self::Callable(x) => write!(f, "{x}"),
+            Self::Class(x) => write!(f, "{x}"),
+            Self::Instance(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+/// Orders an integer against a float without rounding the integer to a float first, which
+/// would make 2^53 + 1 equal to 2^53.
+fn compare_integer_float(integer: i64, float: f64) -> Option<Ordering> {
+    // 2^63, the smallest float too large for an `i64`. Whole floats below it convert exactly.
+    const LIMIT: f64 = 9_223_372_036_854_775_808_f64;
+    if float.is_nan() {
+        return None;
+    }
+    if float >= LIMIT {
+        return Some(Ordering::Less);
+    }
+    if float < -LIMIT {
+        return Some(Ordering::Greater);
+    }
+    // The whole parts decide, unless they are equal and the fraction breaks the tie.
+    match integer.cmp(&(float.trunc() as i64)) {
+        Ordering::Equal => 0_f64.partial_cmp(&float.fract()),
+        ordering => Some(ordering),
+    }
+}
+
+/// Formats a number the way Lox prints it: whole numbers without a fractional part (`3`, not
+/// `3.0`), negative zero as `-0`, and the special values as `NaN`, `Infinity` and `-Infinity`.
+pub fn format_number(x: f64) -> String {
+    if x.is_nan() {
+        "NaN".to_string()
+    } else if x.is_infinite() {
+        if x > 0_f64 { "Infinity" } else { "-Infinity" }.to_string()
+    } else {
+        // Rust already leaves off `.0` and keeps the sign of zero.
+        x.to_string()
+    }
+}
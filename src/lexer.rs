@@ -1,18 +1,87 @@
-use crate::token::{Error, Keyword, Token, TokenKind};
+use crate::span::Span;
+use crate::token::{self, Keyword, Token, TokenKind};
 
+/// A stretch of source that isn't a valid token.
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub kind: token::Error,
+    pub span: Span,
+}
+
+impl std::fmt::Display for token::Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedCharacter => write!(f, "Unexpected character."),
+            Self::UnterminatedString => write!(f, "Unterminated string."),
+        }
+    }
+}
+
+impl token::Error {
+    /// A stable identifier for the kind of error, for tools that consume diagnostics. Codes of
+    /// removed kinds are never reused.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnexpectedCharacter => "E0101",
+            Self::UnterminatedString => "E0102",
+        }
+    }
+}
+
+/// Scans tokens lazily. Invalid tokens are never yielded; they are set aside in `errors` so
+/// that the parser only ever sees well-formed input.
 pub struct Lexer<Chars: Iterator<Item = char>> {
     source: std::iter::Peekable<Chars>,
+    /// Byte offset, line and column of the next character.
+    offset: usize,
     line: u32,
+    column: u32,
+    /// Where the token being scanned started.
+    start: (usize, u32, u32),
+    /// Where the last token ended. The end of input is placed here rather than after any
+    /// trailing whitespace, so that errors about something missing point right after the code.
+    previous_end: (usize, u32, u32),
+    finished: bool,
+    errors: Vec<Error>,
+    /// Where each token yielded right after an invalid one starts.
+    resumed_at: Vec<usize>,
 }
 
 impl<Chars: Iterator<Item = char>> Lexer<Chars> {
     pub fn from_iter(chars: Chars) -> Self {
         Self {
             source: chars.peekable(),
-            line: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+            start: (0, 1, 1),
+            previous_end: (0, 1, 1),
+            finished: false,
+            errors: Vec::new(),
+            resumed_at: Vec::new(),
         }
     }
 
+    /// Consumes the next character if it satisfies `f`, keeping track of where we are.
+    fn advance_if<F>(&mut self, f: F) -> Option<char>
+    where
+        F: FnOnce(&char) -> bool,
+    {
+        let x = self.source.next_if(f)?;
+        self.offset += x.len_utf8();
+        if x == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(x)
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        self.advance_if(|_| true)
+    }
+
     fn is_at_end(&mut self) -> bool {
         self.source.peek().is_none()
     }
@@ -21,16 +90,38 @@ impl<Chars: Iterator<Item = char>> Lexer<Chars> {
     where
         F: FnOnce(&char) -> bool + Copy,
     {
-        while self.source.next_if(f).is_some() {}
+        while self.advance_if(f).is_some() {}
     }
 
-    fn new_token(&self, text: String, kind: TokenKind) -> Token {
-        Token::new(text, self.line, kind)
+    fn skip_whitespace(&mut self) {
+        self.trim_while(|x| x.is_whitespace());
     }
 
-    pub fn next_token(&mut self) -> Option<Token> {
-        self.trim_while(|x| x.is_whitespace());
-        if let Some(x) = self.source.next() {
+    fn new_token(&mut self, text: String, kind: TokenKind) -> Token {
+        self.previous_end = (self.offset, self.line, self.column);
+        let (start, line, column) = self.start;
+        Token::new(text, Span::new(start, self.offset, line, column), kind)
+    }
+
+    /// The lexical errors found in what has been scanned so far, in source order.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Whether `token` was yielded right after an invalid token, which the parser never saw.
+    pub fn follows_error(&self, token: &Token) -> bool {
+        self.resumed_at.contains(&token.span.start)
+    }
+
+    /// Hands over the errors collected so far, leaving none behind.
+    pub fn take_errors(&mut self) -> Vec<Error> {
+        std::mem::take(&mut self.errors)
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        self.skip_whitespace();
+        self.start = (self.offset, self.line, self.column);
+        if let Some(x) = self.advance() {
             let mut text = String::new();
             text.push(x);
             match x {
@@ -39,63 +130,70 @@ impl<Chars: Iterator<Item = char>> Lexer<Chars> {
                 ')' => Some(self.new_token(text, TokenKind::RightParen)),
                 '{' => Some(self.new_token(text, TokenKind::LeftBrace)),
                 '}' => Some(self.new_token(text, TokenKind::RightBrace)),
+                ':' => Some(self.new_token(text, TokenKind::Colon)),
                 ',' => Some(self.new_token(text, TokenKind::Comma)),
                 '.' => Some(self.new_token(text, TokenKind::Dot)),
                 '-' => Some(self.new_token(text, TokenKind::Minus)),
                 '+' => Some(self.new_token(text, TokenKind::Plus)),
                 ';' => Some(self.new_token(text, TokenKind::Semicolon)),
                 '*' => Some(self.new_token(text, TokenKind::Star)),
-                '!' => match self.source.next_if_eq(&'=') {
+                '%' => Some(self.new_token(text, TokenKind::Percent)),
+                '~' => match self.advance_if(|&x| x == '/') {
+                    Some(x) => {
+                        text.push(x);
+                        Some(self.new_token(text, TokenKind::TildeSlash))
+                    }
+                    None => Some(self.new_token(text, TokenKind::Error(token::Error::UnexpectedCharacter))),
+                },
+                '!' => match self.advance_if(|&x| x == '=') {
                     Some(x) => {
                         text.push(x);
                         Some(self.new_token(text, TokenKind::BangEqual))
                     }
                     None => Some(self.new_token(text, TokenKind::Bang)),
                 },
-                '=' => match self.source.next_if_eq(&'=') {
+                '=' => match self.advance_if(|&x| x == '=') {
                     Some(x) => {
                         text.push(x);
                         Some(self.new_token(text, TokenKind::EqualEqual))
                     }
                     None => Some(self.new_token(text, TokenKind::Equal)),
                 },
-                '<' => match self.source.next_if_eq(&'=') {
+                '<' => match self.advance_if(|&x| x == '=') {
                     Some(x) => {
                         text.push(x);
                         Some(self.new_token(text, TokenKind::LessEqual))
                     }
                     None => Some(self.new_token(text, TokenKind::Less)),
                 },
-                '>' => match self.source.next_if_eq(&'=') {
+                '>' => match self.advance_if(|&x| x == '=') {
                     Some(x) => {
                         text.push(x);
                         Some(self.new_token(text, TokenKind::GreatherEqual))
                     }
                     None => Some(self.new_token(text, TokenKind::Greather)),
                 },
-                '/' => match self.source.next_if_eq(&'/') {
+                '/' => match self.advance_if(|&x| x == '/') {
                     Some(_) => {
                         self.trim_while(|&x| x != '\n');
-                        None
+                        self.next_token()
                     }
                     None => Some(self.new_token(text, TokenKind::Slash)),
                 },
                 '"' => {
-                    while let Some(x) = self.source.next_if(|&x| x != '"') {
-                        if x == '\n' {
-                            self.line += 1;
-                        }
+                    while let Some(x) = self.advance_if(|&x| x != '"') {
                         text.push(x)
                     }
 
                     if self.is_at_end() {
-                        return Some(
-                            self.new_token(text, TokenKind::Error(Error::UnterminatedString)),
-                        );
+                        // Point at the opening quote rather than at everything after it.
+                        let mut token = self.new_token(text, TokenKind::Error(token::Error::UnterminatedString));
+                        token.span.end = token.span.start + 1;
+                        return Some(token);
                     }
 
                     // skip the remaining '"'
-                    self.source.next();
+                    self.advance();
 
                     // Trim the surrounding quotes.
                     if !text.is_empty() {
@@ -104,35 +202,55 @@ impl<Chars: Iterator<Item = char>> Lexer<Chars> {
                     Some(self.new_token(text.clone(), TokenKind::String(text)))
                 }
                 '0'..='9' => {
-                    while let Some(x) = self.source.next_if(|&x| x.is_numeric()) {
+                    // Only ASCII digits, so that a following `²` or `٣` is an unexpected
+                    // character rather than part of the number.
+                    while let Some(x) = self.advance_if(|&x| x.is_ascii_digit()) {
                         text.push(x)
                     }
 
-                    if let Some(x) = self.source.next_if(|&x| x == '.') {
+                    if let Some(x) = self.advance_if(|&x| x == '.') {
                         if let Some(&y) = self.source.peek() {
-                            if y.is_numeric() {
+                            if y.is_ascii_digit() {
                                 text.push(x);
                             }
 
-                            while let Some(x) = self.source.next_if(|&x| x.is_numeric()) {
+                            while let Some(x) = self.advance_if(|&x| x.is_ascii_digit()) {
                                 text.push(x)
                             }
                         }
                     }
-                    let token: Token = match text.parse() {
-                        Ok(literal) => self.new_token(text, TokenKind::Number(literal)),
-                        Err(_) => self.new_token(text, TokenKind::Error(Error::UnterminatedString)),
+                    // Literals without a fractional part are integers.
+                    let kind = if text.contains('.') {
+                        match text.parse() {
+                            Ok(literal) => TokenKind::Number(literal),
+                            Err(_) => TokenKind::Error(token::Error::UnexpectedCharacter),
+                        }
+                    } else {
+                        match text.parse() {
+                            Ok(literal) => TokenKind::Integer(literal),
+                            #[cfg(feature = "bigint")]
+                            Err(_) => match text.parse() {
+                                Ok(literal) => TokenKind::BigInteger(literal),
+                                Err(_) => TokenKind::Error(token::Error::UnexpectedCharacter),
+                            },
+                            // The parser rejects this like any other literal too large for an
+                            // `i64`, so there is no need to carry the exact value.
+                            #[cfg(not(feature = "bigint"))]
+                            Err(_) => TokenKind::Integer(u64::MAX),
+                        }
                     };
-                    Some(token)
+                    Some(self.new_token(text, kind))
                 }
-                'a'..='z' | 'A'..='B' | '_' => {
-                    while let Some(x) = self.source.next_if(|&x| x.is_alphanumeric()) {
+                'a'..='z' | 'A'..='Z' | '_' => {
+                    while let Some(x) = self.advance_if(|&x| x.is_alphanumeric() || x == '_') {
                         text.push(x);
                     }
 
                     let keyword = match text.as_str() {
                         "and" => Some(Keyword::And),
+                        "break" => Some(Keyword::Break),
                         "class" => Some(Keyword::Class),
+                        "continue" => Some(Keyword::Continue),
                         "else" => Some(Keyword::Else),
                         "false" => Some(Keyword::False),
                         "for" => Some(Keyword::For),
@@ -155,8 +273,12 @@ impl<Chars: Iterator<Item = char>> Lexer<Chars> {
                         Some(self.new_token(text, TokenKind::Identifiter))
                     }
                 }
-                _ => Some(self.new_token(text, TokenKind::Error(Error::UnexpectedCharacter))),
+                _ => Some(self.new_token(text, TokenKind::Error(token::Error::UnexpectedCharacter))),
             }
+        } else if !self.finished {
+            self.finished = true;
+            self.start = self.previous_end;
+            Some(self.new_token(String::new(), TokenKind::Eof))
         } else {
             None
         }
@@ -167,7 +289,22 @@ impl<Chars: Iterator<Item = char>> Iterator for Lexer<Chars> {
     type Item = Token;
 
     fn next(&mut self) -> Option<Token> {
-        self.next_token()
+        let mut after_error = false;
+        loop {
+            let token = self.next_token()?;
+            match token.kind {
+                TokenKind::Error(kind) => {
+                    self.errors.push(Error { kind, span: token.span });
+                    after_error = true;
+                }
+                _ => {
+                    if after_error {
+                        self.resumed_at.push(token.span.start);
+                    }
+                    return Some(token);
+                }
+            }
+        }
     }
 }
 
@@ -178,9 +315,9 @@ mod tests {
     #[test]
     fn scan_expresion_multiply() {
         let mut scanner = Lexer::from_iter("2*2".chars());
-        assert_eq!(scanner.next().unwrap().kind, TokenKind::Number(2_f64));
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Integer(2));
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Star);
-        assert_eq!(scanner.next().unwrap().kind, TokenKind::Number(2_f64));
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Integer(2));
     }
 
     #[test]
@@ -205,6 +342,12 @@ mod tests {
         let token = scanner.next().expect("Should be some");
         assert_eq!(token.kind, TokenKind::Identifiter);
         assert_eq!(token.lexeme, "blafs");
+
+        let source = "Snake_case_2".chars();
+        let mut scanner = Lexer::from_iter(source);
+        let token = scanner.next().expect("Should be some");
+        assert_eq!(token.kind, TokenKind::Identifiter);
+        assert_eq!(token.lexeme, "Snake_case_2");
     }
 
     #[test]
@@ -216,12 +359,20 @@ mod tests {
         assert_eq!(token.lexeme, "or");
     }
 
+    #[test]
+    fn scan_loop_jump_keywords() {
+        let source = "break continue".chars();
+        let mut scanner = Lexer::from_iter(source);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Keyword(Keyword::Break));
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Keyword(Keyword::Continue));
+    }
+
     #[test]
     fn scan_numeric_literals() {
         let source = "1234".chars();
         let mut scanner = Lexer::from_iter(source);
         let token = scanner.next().expect("Should be some");
-        assert_eq!(token.kind, TokenKind::Number(1234_f64));
+        assert_eq!(token.kind, TokenKind::Integer(1234));
         assert_eq!(token.lexeme, "1234", "Should handle integers");
 
         let source = "12.34".chars();
@@ -231,6 +382,52 @@ mod tests {
         assert_eq!(token.lexeme, "12.34", "Should handle floats");
     }
 
+    #[test]
+    #[cfg(not(feature = "bigint"))]
+    fn scan_numeric_literals_too_large() {
+        let source = "9223372036854775808 99999999999999999999999".chars();
+        let mut scanner = Lexer::from_iter(source);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Integer(1 << 63));
+        let token = scanner.next().unwrap();
+        assert_eq!(token.kind, TokenKind::Integer(u64::MAX), "Left for the parser to reject");
+        assert_eq!(token.lexeme, "99999999999999999999999");
+        assert!(scanner.errors().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn scan_numeric_literals_of_any_length() {
+        let source = "18446744073709551616".chars();
+        let mut scanner = Lexer::from_iter(source);
+        let expect: num_bigint::BigInt = "18446744073709551616".parse().unwrap();
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::BigInteger(expect));
+        assert!(scanner.errors().is_empty());
+    }
+
+    #[test]
+    fn scan_numeric_literals_stop_at_non_ascii_digits() {
+        for (source, expect) in [("1.5²", TokenKind::Number(1.5)), ("1²", TokenKind::Integer(1))] {
+            let mut scanner = Lexer::from_iter(source.chars());
+            let kinds: Vec<TokenKind> = scanner.by_ref().map(|x| x.kind).collect();
+            assert_eq!(kinds, vec![expect, TokenKind::Eof], "{source}");
+            let errors: Vec<(token::Error, usize)> =
+                scanner.errors().iter().map(|x| (x.kind.clone(), x.span.start)).collect();
+            assert_eq!(errors, vec![(token::Error::UnexpectedCharacter, source.len() - 2)], "{source}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn scan_numeric_literals_of_any_length_stop_at_non_ascii_digits() {
+        let source = "18446744073709551616²".chars();
+        let mut scanner = Lexer::from_iter(source);
+        let expect: num_bigint::BigInt = "18446744073709551616".parse().unwrap();
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::BigInteger(expect));
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Eof);
+        assert_eq!(scanner.errors().len(), 1);
+        assert_eq!(scanner.errors()[0].kind, token::Error::UnexpectedCharacter);
+    }
+
     #[test]
     fn scan_string_literals_ok() {
         let source = "\"Hello, \nWorld!\"".chars();
@@ -238,34 +435,50 @@ mod tests {
         let token = scanner.next().expect("Should be some");
         assert_eq!(token.kind, TokenKind::String(token.lexeme.clone()));
         assert_eq!(token.lexeme, "Hello, \nWorld!");
-        assert_eq!(token.line, 1);
+        assert_eq!(token.span.line, 1, "Spans start at the opening quote");
+        assert_eq!(scanner.next().unwrap().span.line, 2);
     }
 
     #[test]
     fn scan_string_literals_unterminated() {
-        let source = "\"".chars();
+        let source = "print \"abc\n;".chars();
         let mut scanner = Lexer::from_iter(source);
-        let token = scanner.next().expect("Should be some");
-        assert_eq!(token.kind, TokenKind::Error(Error::UnterminatedString));
-        assert_eq!(token.lexeme, "\"");
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Keyword(Keyword::Print));
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Eof, "Errors are not yielded");
+        let [err] = scanner.errors() else {
+            panic!("Expected one error, found {:?}", scanner.errors());
+        };
+        assert_eq!(err.kind, token::Error::UnterminatedString);
+        assert_eq!(
+            (err.span.start, err.span.end, err.span.line, err.span.column),
+            (6, 7, 1, 7),
+            "Points at the opening quote"
+        );
     }
 
     #[test]
     fn scan_error() {
-        let source = "@".chars();
+        let source = "1 @ # 2".chars();
         let mut scanner = Lexer::from_iter(source);
-        let token = scanner.next().expect("Should be some");
-        assert_eq!(token.kind, TokenKind::Error(Error::UnexpectedCharacter));
+        let kinds: Vec<TokenKind> = scanner.by_ref().map(|x| x.kind).collect();
+        assert_eq!(kinds, vec![TokenKind::Integer(1), TokenKind::Integer(2), TokenKind::Eof]);
+        let errors: Vec<(token::Error, usize)> =
+            scanner.errors().iter().map(|x| (x.kind.clone(), x.span.start)).collect();
+        assert_eq!(
+            errors,
+            vec![(token::Error::UnexpectedCharacter, 2), (token::Error::UnexpectedCharacter, 4)]
+        );
     }
 
     #[test]
     fn scan_single_character_tokens() {
-        let source = "() {} , . - + ;  * /".chars();
+        let source = "() {} : , . - + ;  * / % ~/".chars();
         let mut scanner = Lexer::from_iter(source);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::LeftParen);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::RightParen);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::LeftBrace);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::RightBrace);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Colon);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Comma);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Dot);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Minus);
@@ -273,6 +486,9 @@ mod tests {
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Semicolon);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Star);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Slash);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Percent);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::TildeSlash);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Eof);
         assert!(scanner.next().is_none(), "End of file");
     }
 
@@ -298,15 +514,46 @@ mod tests {
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Greather);
     }
 
+    #[test]
+    fn scan_line_numbers() {
+        let source = "a\n\nb // comment\nc".chars();
+        let lines: Vec<u32> = Lexer::from_iter(source).map(|x| x.span.line).collect();
+        assert_eq!(lines, vec![1, 3, 4, 4]);
+    }
+
+    #[test]
+    fn scan_spans() {
+        let source = "var s = \"é\";\n  s".chars();
+        let spans: Vec<(usize, usize, u32, u32)> = Lexer::from_iter(source)
+            .map(|x| (x.span.start, x.span.end, x.span.line, x.span.column))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 3, 1, 1),
+                (4, 5, 1, 5),
+                (6, 7, 1, 7),
+                (8, 12, 1, 9),
+                (12, 13, 1, 12),
+                (16, 17, 2, 3),
+                (17, 17, 2, 4),
+            ]
+        );
+    }
+
     #[test]
     fn scan_comment() {
         let source = "/ //This is a comment".chars();
         let mut scanner = Lexer::from_iter(source);
         assert_eq!(scanner.next().unwrap().kind, TokenKind::Slash);
-        assert!(scanner.next().is_none(), "Comment should be discarded");
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Eof, "Comment should be discarded");
 
         let source = "//This is a comment".chars();
         let mut scanner = Lexer::from_iter(source);
-        assert!(scanner.next().is_none(), "Comment should be discarded");
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Eof, "Comment should be discarded");
+
+        let source = "// comment\n+".chars();
+        let mut scanner = Lexer::from_iter(source);
+        assert_eq!(scanner.next().unwrap().kind, TokenKind::Plus, "Scanning continues after a comment");
     }
 }
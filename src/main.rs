@@ -1,52 +1,176 @@
+mod diagnostic;
+mod error;
 mod expression;
+mod interpreter;
 mod lexer;
 mod parser;
+mod resolver;
+mod span;
+mod statement;
+mod suggest;
 mod token;
-mod interpreter;
-
-const EX_USAGE: i32 = 64;
 
 const PREFIX: &str = ">";
 
-fn run_file(file_path: &String) -> Result<(), expression::Error> {
+/// Stands in for a file name when reporting errors in code typed at the prompt.
+const PROMPT_FILE: &str = "<stdin>";
+
+const ERROR_FORMAT_FLAG: &str = "--error-format=";
+const STRING_COERCION_FLAG: &str = "--string-coercion";
+
+/// What the command line asked for, besides the script to run.
+struct Options {
+    format: ErrorFormat,
+    /// Whether `"n = " + 3` converts the number rather than failing.
+    string_coercion: bool,
+}
+
+impl Options {
+    /// Reads the `--` flags, or fails on one it doesn't know.
+    fn parse(flags: &[String]) -> Option<Self> {
+        let mut options = Self {
+            format: ErrorFormat::Human,
+            string_coercion: false,
+        };
+        for flag in flags {
+            match flag.strip_prefix(ERROR_FORMAT_FLAG) {
+                Some("human") => options.format = ErrorFormat::Human,
+                Some("json") => options.format = ErrorFormat::Json,
+                Some(_) => return None,
+                None if flag == STRING_COERCION_FLAG => options.string_coercion = true,
+                None => return None,
+            }
+        }
+        Some(options)
+    }
+
+    fn interpreter(&self) -> interpreter::Interpreter<std::io::Stdout> {
+        interpreter::Interpreter::new(std::io::stdout()).with_string_coercion(self.string_coercion)
+    }
+}
+
+/// How errors are reported, picked with `--error-format=human|json`.
+#[derive(Clone, Copy)]
+enum ErrorFormat {
+    /// Source snippets with the error underlined, on stdout.
+    Human,
+    /// One JSON object per line, on stderr, for editors and other tools.
+    Json,
+}
+
+fn run_file(file_path: &String, options: &Options) -> Result<(), Vec<error::Error>> {
+    let format = options.format;
     println!("->> FILE MODE\n");
-    let source =
-        std::fs::read_to_string(file_path).expect("Should have been able to read the file");
-    run(&source)?;
-    Ok(())
+    let source = std::fs::read_to_string(file_path).map_err(|source| {
+        let err = error::Error::Io {
+            path: file_path.clone(),
+            source,
+        };
+        report(file_path, "", &err, format);
+        vec![err]
+    })?;
+    let mut interpreter = options.interpreter();
+    run(file_path, &source, &mut interpreter, format)
 }
 
-fn run_prompt() -> Result<(), expression::Error> {
+fn run_prompt(options: &Options) -> Result<(), Vec<error::Error>> {
+    let format = options.format;
+    let mut interpreter = options.interpreter();
     loop {
         print!("{PREFIX} ");
         std::io::Write::flush(&mut std::io::stdout()).expect("flush failed!");
         let mut source = String::new();
-        std::io::stdin().read_line(&mut source).unwrap(); //TODO: remove unwrap
-        match run(&source) {
-            Ok(expr) => println!("Debug| {expr}"),
-            Err(err) => println!("{:?}", err),
+        let read = std::io::stdin().read_line(&mut source).map_err(|source| {
+            vec![error::Error::Io {
+                path: PROMPT_FILE.to_string(),
+                source,
+            }]
+        })?;
+        if read == 0 {
+            println!();
+            return Ok(());
         }
+        // Errors are reported as they happen; the prompt just carries on.
+        let _ = run(PROMPT_FILE, &source, &mut interpreter, format);
     }
 }
 
-fn print_usage() -> Result<(), expression::Error> {
-    println!("Usage: jrox [script]");
-    std::process::exit(EX_USAGE);
+fn print_usage() -> Result<(), Vec<error::Error>> {
+    let err = error::Error::Usage;
+    println!("{err}");
+    Err(vec![err])
 }
 
-fn run(source: &str) -> Result<expression::Expr, expression::Error> {
-    let chars = source.chars();
-    let mut tokens = lexer::Lexer::from_iter(chars).peekable();
-    crate::parser::parse(&mut tokens)
+fn run<W: std::io::Write>(
+    file: &str,
+    source: &str,
+    interpreter: &mut interpreter::Interpreter<W>,
+    format: ErrorFormat,
+) -> Result<(), Vec<error::Error>> {
+    let mut lexer = lexer::Lexer::from_iter(source.chars());
+    let parsed = crate::parser::parse(&mut lexer.by_ref().peekable());
+    let statements = match parsed {
+        Ok(statements) if lexer.errors().is_empty() => statements,
+        parsed => {
+            // Lexical and syntax errors are reported together, in the order they occur.
+            let errors = error::syntax_errors(&mut lexer, parsed.err().unwrap_or_default());
+            for err in &errors {
+                report(file, source, err, format);
+            }
+            return Err(errors);
+        }
+    };
+    let result = resolver::resolve(&statements)
+        .map_err(error::Error::from)
+        .and_then(|()| {
+            interpreter
+                .interpret(&statements)
+                .map_err(error::Error::from)
+        });
+    result.map_err(|err| {
+        report(file, source, &err, format);
+        vec![err]
+    })
 }
 
-fn main() -> Result<(), expression::Error> {
-    println!("->> Welcome to Rox!");
-    let args: Vec<String> = std::env::args().collect();
+/// Prints `err` as a diagnostic in the requested format. Human-readable diagnostics are in
+/// colour when stdout is a terminal.
+fn report(file: &str, source: &str, err: &error::Error, format: ErrorFormat) {
+    let diagnostic = err.diagnostic();
+    match format {
+        ErrorFormat::Human => {
+            let colour = std::io::IsTerminal::is_terminal(&std::io::stdout());
+            print!("{}", diagnostic.render(file, source, colour));
+        }
+        ErrorFormat::Json => eprintln!("{}", diagnostic.to_json(file)),
+    }
+}
 
-    match args.len().cmp(&2) {
-        std::cmp::Ordering::Greater => print_usage(),
-        std::cmp::Ordering::Equal => run_file(&args[1]),
-        std::cmp::Ordering::Less => run_prompt(),
+fn main() -> std::process::ExitCode {
+    // Deeply recursive scripts need more stack than the main thread has.
+    std::thread::Builder::new()
+        .stack_size(interpreter::STACK_SIZE)
+        .spawn(start)
+        .expect("failed to start the interpreter thread")
+        .join()
+        .unwrap_or(std::process::ExitCode::FAILURE)
+}
+
+fn start() -> std::process::ExitCode {
+    println!("->> Welcome to Rox!");
+    let (flags, args): (Vec<String>, Vec<String>) =
+        std::env::args().skip(1).partition(|x| x.starts_with("--"));
+    let result = match (Options::parse(&flags), args.as_slice()) {
+        (Some(options), [file]) => run_file(file, &options),
+        (Some(options), []) => run_prompt(&options),
+        _ => print_usage(),
+    };
+    // Errors have already been reported by the time they get here; the first one decides the
+    // exit code.
+    match result {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(errors) => errors
+            .first()
+            .map_or(std::process::ExitCode::FAILURE, |x| x.exit_code().into()),
     }
 }
@@ -0,0 +1,113 @@
+use crate::expression::{Error, ErrorKind};
+use crate::statement::{JumpStatement, Stmt};
+
+/// Checks that every `break` and `continue` is nested inside a loop and, when labeled, inside
+/// a loop carrying that label. Every misplaced jump is added to `errors`.
+pub fn check_jumps(statements: &[Stmt], errors: &mut Vec<Error>) {
+    let mut labels = Vec::new();
+    for statement in statements {
+        check_statement(statement, &mut labels, errors);
+    }
+}
+
+/// `labels` holds one entry per enclosing loop, innermost last.
+fn check_statement(stmt: &Stmt, labels: &mut Vec<Option<String>>, errors: &mut Vec<Error>) {
+    match stmt {
+        Stmt::Block(statements) => {
+            for statement in statements {
+                check_statement(statement, labels, errors);
+            }
+        }
+        Stmt::If(stmt) => {
+            check_statement(stmt.then_branch(), labels, errors);
+            if let Some(else_branch) = stmt.else_branch() {
+                check_statement(else_branch, labels, errors);
+            }
+        }
+        Stmt::While(stmt) => {
+            labels.push(stmt.label().map(|x| x.lexeme.clone()));
+            check_statement(stmt.body(), labels, errors);
+            labels.pop();
+        }
+        Stmt::Break(stmt) => errors.extend(check_jump(stmt, labels, ErrorKind::BreakOutsideLoop)),
+        Stmt::Continue(stmt) => {
+            errors.extend(check_jump(stmt, labels, ErrorKind::ContinueOutsideLoop))
+        }
+        // A function body starts over: loops around the declaration can't be jumped out of.
+        Stmt::Function(stmt) => check_jumps(stmt.body(), errors),
+        Stmt::Class(stmt) => {
+            for method in stmt.methods() {
+                check_jumps(method.body(), errors);
+            }
+        }
+        Stmt::Expression(_) | Stmt::Print(_) | Stmt::Return(_) | Stmt::Var(_) => {}
+    }
+}
+
+fn check_jump(stmt: &JumpStatement, labels: &[Option<String>], outside: ErrorKind) -> Option<Error> {
+    if labels.is_empty() {
+        return Some(outside.at(stmt.keyword().span));
+    }
+    match stmt.label() {
+        Some(label) if !labels.iter().flatten().any(|x| *x == label.lexeme) => {
+            Some(ErrorKind::UndefinedLabel(label.lexeme.clone()).at(label.span))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expression::ErrorKind;
+    use crate::lexer::Lexer;
+    use crate::parser::parse;
+
+    /// Parses `source` and returns the kinds of all errors found.
+    fn check(source: &str) -> Result<(), Vec<ErrorKind>> {
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        parse(&mut tokens)
+            .map(|_| ())
+            .map_err(|errors| errors.into_iter().map(|x| x.kind).collect())
+    }
+
+    #[test]
+    fn test_parser_loops_jump_inside_loop() {
+        assert!(check("while (true) break;").is_ok());
+        assert!(check("for (;;) { if (true) continue; }").is_ok());
+        assert!(check("outer: while (true) while (true) break outer;").is_ok());
+    }
+
+    #[test]
+    fn test_parser_loops_jump_outside_loop() {
+        assert!(matches!(check("break;").unwrap_err()[..], [ErrorKind::BreakOutsideLoop]));
+        assert!(matches!(check("{ continue; }").unwrap_err()[..], [ErrorKind::ContinueOutsideLoop]));
+        assert!(matches!(
+            check("while (true) {} break;").unwrap_err()[..],
+            [ErrorKind::BreakOutsideLoop]
+        ));
+        assert!(matches!(
+            check("while (true) { fun f() { break; } }").unwrap_err()[..],
+            [ErrorKind::BreakOutsideLoop]
+        ));
+    }
+
+    #[test]
+    fn test_parser_loops_undefined_label() {
+        assert!(matches!(
+            &check("while (true) break outer;").unwrap_err()[..],
+            [ErrorKind::UndefinedLabel(label)] if label == "outer"
+        ));
+        assert!(matches!(
+            &check("outer: while (true) {} inner: while (true) continue outer;").unwrap_err()[..],
+            [ErrorKind::UndefinedLabel(label)] if label == "outer"
+        ));
+    }
+
+    #[test]
+    fn test_parser_loops_reports_every_jump() {
+        assert!(matches!(
+            check("break; { continue; } while (true) break missing;").unwrap_err()[..],
+            [ErrorKind::BreakOutsideLoop, ErrorKind::ContinueOutsideLoop, ErrorKind::UndefinedLabel(_)]
+        ));
+    }
+}
@@ -1,95 +1,265 @@
+mod loops;
 mod parse;
+mod statement;
 
-use crate::expression::{Error, Expr};
+use crate::expression::{Error, ErrorKind};
+use crate::statement::Stmt;
 use crate::token::{Token, TokenKind, Keyword};
 
+/// Parses a whole program. A syntax error doesn't stop parsing: the rest of the program is
+/// still parsed so that every error can be reported at once, in source order.
 pub fn parse<I: Iterator<Item = Token>>(
     tokens: &mut std::iter::Peekable<I>,
-) -> Result<Expr, Error> {
-    parse::parse_expression(tokens)
+) -> Result<Vec<Stmt>, Vec<Error>> {
+    let mut statements = Vec::new();
+    let mut errors = Vec::new();
+    while !is_at_end(tokens) {
+        if let Some(statement) = statement::parse_declaration_or_recover(tokens, &mut errors, false) {
+            statements.push(statement);
+        }
+    }
+    loops::check_jumps(&statements, &mut errors);
+    if !errors.is_empty() {
+        errors.sort_by_key(|x| x.span.start);
+        return Err(errors);
+    }
+    Ok(statements)
+}
+
+fn is_at_end<I: Iterator<Item = Token>>(tokens: &mut std::iter::Peekable<I>) -> bool {
+    tokens.peek().is_none_or(|x| x.kind == TokenKind::Eof)
+}
+
+/// Builds an error located at the next token, which is where parsing got stuck. Errors about a
+/// missing token also say what was there instead.
+fn error_at<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    kind: ErrorKind,
+) -> Error {
+    match tokens.peek() {
+        Some(token) if !kind.expected().is_empty() => kind.found(token.clone()),
+        token => kind.at(token.map(|x| x.span).unwrap_or_default()),
+    }
+}
+
+/// Consumes the next token if it is a `kind`, and fails with `error` otherwise.
+fn consume<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    kind: TokenKind,
+    error: ErrorKind,
+) -> Result<Token, Error> {
+    match tokens.next_if(|x| x.kind == kind) {
+        Some(token) => Ok(token),
+        None => Err(error_at(tokens, error)),
+    }
 }
 
-#[allow(dead_code)]
-pub fn syncronize<I: Iterator<Item = Token>>( tokens: &mut std::iter::Peekable<I>) {
-    // Advance
-    while tokens.peek().is_some(){
-        let x = tokens.next_if(|x| {
-            !matches!(x.kind, TokenKind::Semicolon
-                    | TokenKind::Keyword(Keyword::Class) 
-                    | TokenKind::Keyword(Keyword::Fun) 
-                    | TokenKind::Keyword(Keyword::Var) 
-                    | TokenKind::Keyword(Keyword::For) 
-                    | TokenKind::Keyword(Keyword::If) 
-                    | TokenKind::Keyword(Keyword::While) 
-                    | TokenKind::Keyword(Keyword::Print) 
-                    | TokenKind::Keyword(Keyword::Return) 
-            )
-        });
-        if x.is_none() {
-            let _ = tokens.next_if(|x| x.kind == TokenKind::Semicolon);
-            return;
+/// Skips the rest of a statement after a syntax error, stopping at the start of the next one
+/// so that parsing can carry on from there. Braces opened along the way, such as those of a
+/// function body after a broken header, are skipped up to their matching `}`.
+///
+/// Inside a block, an unmatched `}` closes that block and is left for it. At the top level
+/// there is nothing for it to close, so it is skipped too.
+pub fn syncronize<I: Iterator<Item = Token>>(tokens: &mut std::iter::Peekable<I>, in_block: bool) {
+    let mut depth = 0;
+    while let Some(token) = tokens.peek() {
+        match token.kind {
+            TokenKind::Eof => return,
+            TokenKind::Semicolon if depth == 0 => {
+                tokens.next();
+                return;
+            }
+            TokenKind::RightBrace if depth == 0 && in_block => return,
+            TokenKind::Keyword(
+                Keyword::Class
+                | Keyword::Fun
+                | Keyword::Var
+                | Keyword::For
+                | Keyword::If
+                | Keyword::While
+                | Keyword::Print
+                | Keyword::Return,
+            ) if depth == 0 => return,
+            TokenKind::LeftBrace => depth += 1,
+            TokenKind::RightBrace => depth = usize::saturating_sub(depth, 1),
+            _ => {}
         }
+        tokens.next();
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::expression::{Expr, BinaryExpression, LiteralExpression};
+    use crate::expression::{Expr, BinaryExpression, ErrorKind, LiteralExpression};
     use crate::lexer::Lexer;
+    use crate::statement::Stmt;
+    use crate::token::TokenKind;
 
     use super::{parse, syncronize};
 
+    /// Asserts that two trees are the same apart from their spans, so that the expected tree
+    /// can be built by hand without working out where each node sits in the source.
+    #[track_caller]
+    pub(super) fn assert_same_tree<T: std::fmt::Debug>(left: T, right: T) {
+        fn without_spans(tree: impl std::fmt::Debug) -> String {
+            let mut debug = format!("{tree:#?}");
+            while let Some(start) = debug.find("Span {") {
+                let end = start + debug[start..].find('}').unwrap();
+                debug.replace_range(start..=end, "_");
+            }
+            debug
+        }
+        assert_eq!(without_spans(left), without_spans(right));
+    }
+
     #[test]
     fn test_parser_expression_presidence_add_mult() {
-        let mut tokens = Lexer::from_iter("2 + 2 * 2".chars()).peekable();
+        let mut tokens = Lexer::from_iter("2 + 2 * 2;".chars()).peekable();
         let expect = Expr::Arithmetic(BinaryExpression::add(
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
             Expr::Arithmetic(BinaryExpression::mult(
-                Expr::Literal(LiteralExpression::number(2_f64)),
-                Expr::Literal(LiteralExpression::number(2_f64)),
+                Expr::Literal(LiteralExpression::integer(2)),
+                Expr::Literal(LiteralExpression::integer(2)),
             )),
         ));
-        assert_eq!(expect, parse(&mut tokens).unwrap());
+        assert_same_tree(vec![Stmt::Expression(expect)], parse(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_expression_presidence_mult_add() {
-        let mut tokens = Lexer::from_iter("2 * 2 + 2".chars()).peekable();
+        let mut tokens = Lexer::from_iter("2 * 2 + 2;".chars()).peekable();
         let expect = Expr::Arithmetic(BinaryExpression::add(
             Expr::Arithmetic(BinaryExpression::mult(
-                Expr::Literal(LiteralExpression::number(2_f64)),
-                Expr::Literal(LiteralExpression::number(2_f64)),
+                Expr::Literal(LiteralExpression::integer(2)),
+                Expr::Literal(LiteralExpression::integer(2)),
             )),
-            Expr::Literal(LiteralExpression::number(2_f64))
+            Expr::Literal(LiteralExpression::integer(2))
         ));
-        assert_eq!(expect, parse(&mut tokens).unwrap());
+        assert_same_tree(vec![Stmt::Expression(expect)], parse(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_expression() {
-        let mut tokens = Lexer::from_iter("2 < 3 >= 1 == false".chars()).peekable();
+        let mut tokens = Lexer::from_iter("2 < 3 >= 1 == false;".chars()).peekable();
         let left = Expr::Compare( BinaryExpression::greater_equal(
             Expr::Compare(BinaryExpression::less(
-               Expr::Literal(LiteralExpression::number(2_f64)),
-               Expr::Literal(LiteralExpression::number(3_f64)),
+               Expr::Literal(LiteralExpression::integer(2)),
+               Expr::Literal(LiteralExpression::integer(3)),
             )),
-            Expr::Literal(LiteralExpression::number(1_f64)),
+            Expr::Literal(LiteralExpression::integer(1)),
         ));
         let right = Expr::Literal(LiteralExpression::boolean(false));
         let expect = Expr::Equality(BinaryExpression::equal(left, right));
         let result = parse(&mut tokens).unwrap();
-        assert_eq!(expect, result);
+        assert_same_tree(vec![Stmt::Expression(expect)], result);
     }
 
     #[test]
     fn test_parser_parse_syncronize() {
-        let mut tokens = Lexer::from_iter("x == 2; 2 == 2".chars()).peekable();
-        syncronize(&mut tokens);
+        let mut tokens = Lexer::from_iter("x == 2; 2 == 2;".chars()).peekable();
+        syncronize(&mut tokens, false);
         let expect = Expr::Equality( BinaryExpression::equal(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse(&mut tokens).unwrap());
+        assert_same_tree(vec![Stmt::Expression(expect)], parse(&mut tokens).unwrap());
     }
 
+    #[test]
+    fn test_parser_parse_reports_every_error() {
+        let source = "var = 1;\nprint 2\nvar ok = 3;\n{ print ; print ok; }\nprint 4;";
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        let errors = parse(&mut tokens).unwrap_err();
+        let lines: Vec<u32> = errors.iter().map(|x| x.span.line).collect();
+        assert_eq!(lines, vec![1, 3, 4]);
+        assert!(matches!(
+            errors.iter().map(|x| &x.kind).collect::<Vec<_>>()[..],
+            [ErrorKind::ExpectVariableName, ErrorKind::ExpectSemicolonAfterValue, ErrorKind::ExpectExpression]
+        ));
+    }
+
+    #[test]
+    fn test_parser_errors_say_what_was_expected_and_found() {
+        let message = |source: &str| {
+            let mut tokens = Lexer::from_iter(source.chars()).peekable();
+            let errors = parse(&mut tokens).unwrap_err();
+            errors.iter().map(|x| x.message()).collect::<Vec<_>>()
+        };
+        assert_eq!(message("print 1 print 2;"), ["Expect ';' after value, found 'print'."]);
+        assert_eq!(message("print ;"), ["Expect expression, found ';'."]);
+        assert_eq!(message("print (1;"), ["Expect ')' after expression, found ';'."]);
+        assert_eq!(message("f(1;"), ["Expect ')' after arguments, found ';'."]);
+        assert_eq!(message("fun f(a;"), ["Expect ')' after parameters, found ';'."]);
+        assert_eq!(message("fun f;"), ["Expect '(' after function name, found ';'."]);
+        assert_eq!(message("if (x print x;"), ["Expect ')' after condition, found 'print'."]);
+        assert_eq!(message("var x = 1 print x;"), ["Expect ';' after variable declaration, found 'print'."]);
+        assert_eq!(message("f() g();"), ["Expect ';' after expression, found 'g'."]);
+        assert_eq!(message("print 1 +"), ["Expect expression, found end of file."]);
+        assert_eq!(message("1 = 2;"), ["Invalid assignment target."]);
+
+        let mut tokens = Lexer::from_iter("var 1;".chars()).peekable();
+        let errors = parse(&mut tokens).unwrap_err();
+        assert_eq!(errors[0].expected(), vec![TokenKind::Identifiter]);
+        assert_eq!(errors[0].found.as_ref().map(|x| x.kind.clone()), Some(TokenKind::Integer(1)));
+    }
+
+    #[test]
+    fn test_parser_integer_literals() {
+        let parse_source = |source: &str| parse(&mut Lexer::from_iter(source.chars()).peekable());
+        let expect = vec![Stmt::Print(Expr::Literal(LiteralExpression::integer(i64::MIN)))];
+        assert_same_tree(parse_source("print -9223372036854775808;").unwrap(), expect);
+
+        if cfg!(not(feature = "bigint")) {
+            for source in ["print 9223372036854775808;", "print 1 + 99999999999999999999999;"] {
+                let errors = parse_source(source).unwrap_err();
+                assert_eq!(errors.len(), 1, "{errors:?}");
+                assert!(matches!(errors[0].kind, ErrorKind::IntegerTooLarge));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parser_parse_syncronize_stops_at_block_end() {
+        let mut tokens = Lexer::from_iter("{ print 1 } print 2;".chars()).peekable();
+        let errors = parse(&mut tokens).unwrap_err();
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert!(matches!(errors[0].kind, ErrorKind::ExpectSemicolonAfterValue));
+    }
+
+    #[test]
+    fn test_parser_parse_syncronize_skips_broken_declarations() {
+        for source in ["fun f(a b) {}", "class A { 1 }", "fun f(a b) { print 1; }", "{ fun f(a b) {} }", "}"] {
+            let mut tokens = Lexer::from_iter(source.chars()).peekable();
+            let errors = parse(&mut tokens).unwrap_err();
+            assert_eq!(errors.len(), 1, "{source}: {errors:?}");
+        }
+
+        let mut tokens = Lexer::from_iter("{ fun f(a b) {} print 1 } print 2;".chars()).peekable();
+        let errors = parse(&mut tokens).unwrap_err();
+        assert_eq!(errors.len(), 2, "Both errors are inside the block: {errors:?}");
+        assert!(matches!(errors[1].kind, ErrorKind::ExpectSemicolonAfterValue));
+
+        let mut tokens = Lexer::from_iter("class A { f(a b) {} g() {} } print 1 print 2;".chars()).peekable();
+        let errors = parse(&mut tokens).unwrap_err();
+        assert_eq!(errors.len(), 2, "Parsing resumes after the class: {errors:?}");
+        assert_eq!(errors[1].span.column, 38);
+    }
+
+    #[test]
+    fn test_parser_never_sees_lexical_errors() {
+        let mut lexer = Lexer::from_iter("print 1 @;".chars());
+        let expect = vec![Stmt::Print(Expr::Literal(LiteralExpression::integer(1)))];
+        assert_same_tree(expect, parse(&mut lexer.by_ref().peekable()).unwrap());
+        assert_eq!(lexer.errors().len(), 1);
+    }
+
+    #[test]
+    fn test_parser_parse_program() {
+        let mut tokens = Lexer::from_iter("print 1; 2;".chars()).peekable();
+        let expect = vec![
+            Stmt::Print(Expr::Literal(LiteralExpression::integer(1))),
+            Stmt::Expression(Expr::Literal(LiteralExpression::integer(2))),
+        ];
+        assert_same_tree(expect, parse(&mut tokens).unwrap());
+    }
 }
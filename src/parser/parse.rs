@@ -1,12 +1,52 @@
     // TODO: Parsing can be cone much better!!
 
-    use crate::expression::{Error, Expr, BinaryExpression, LiteralExpression, UnaryExpression};
+    use super::{consume, error_at};
+    use crate::expression::{Error, ErrorKind, Expr, AssignExpression, BinaryExpression, CallExpression, GetExpression, GroupingExpression, LiteralExpression, LogicalExpression, SetExpression, SuperExpression, ThisExpression, UnaryExpression, VariableExpression, MAX_ARGUMENTS};
     use crate::token::{Keyword, Token, TokenKind};
 
     pub fn parse_expression<I: Iterator<Item = Token>>(
         tokens: &mut std::iter::Peekable<I>,
     ) -> Result<Expr, Error> {
-        parse_equality(tokens)
+        parse_assignment(tokens)
+    }
+
+    pub fn parse_assignment<I: Iterator<Item = Token>>(
+        tokens: &mut std::iter::Peekable<I>,
+    ) -> Result<Expr, Error> {
+        let target = parse_or(tokens)?;
+        if tokens.next_if(|x| x.kind == TokenKind::Equal).is_none() {
+            return Ok(target);
+        }
+
+        // Recurse instead of looping so that `a = b = c` groups as `a = (b = c)`.
+        let value = parse_assignment(tokens)?;
+        match target {
+            Expr::Variable(variable) => Ok(Expr::Assign(AssignExpression::new(variable.name().clone(), value))),
+            Expr::Get(get) => Ok(Expr::Set(SetExpression::new(get.object().clone(), get.name().clone(), value))),
+            target => Err(ErrorKind::InvalidAssignmentTarget.at(target.span())),
+        }
+    }
+
+    pub fn parse_or<I: Iterator<Item = Token>>(
+        tokens: &mut std::iter::Peekable<I>,
+    ) -> Result<Expr, Error> {
+        let mut left = parse_and(tokens)?;
+        while tokens.next_if(|x| x.kind == TokenKind::Keyword(Keyword::Or)).is_some() {
+            let right = parse_and(tokens)?;
+            left = Expr::Logical(LogicalExpression::or(left, right));
+        }
+        Ok(left)
+    }
+
+    pub fn parse_and<I: Iterator<Item = Token>>(
+        tokens: &mut std::iter::Peekable<I>,
+    ) -> Result<Expr, Error> {
+        let mut left = parse_equality(tokens)?;
+        while tokens.next_if(|x| x.kind == TokenKind::Keyword(Keyword::And)).is_some() {
+            let right = parse_equality(tokens)?;
+            left = Expr::Logical(LogicalExpression::and(left, right));
+        }
+        Ok(left)
     }
 
     pub fn parse_equality<I: Iterator<Item = Token>>(
@@ -25,7 +65,7 @@
                     TokenKind::BangEqual => {
                         Expr::Equality(BinaryExpression::not_equal(left, right))
                     }
-                    token => return Err(Error::UnexpecedCharacter(token)),
+                    _ => unreachable!("only equality operators are matched"),
                 }
             }
             None => left,
@@ -52,7 +92,7 @@
                     TokenKind::LessEqual => {
                         Expr::Compare(BinaryExpression::less_equal(left, right))
                     }
-                    token => return Err(Error::UnexpecedCharacter(token)),
+                    _ => unreachable!("only comparison operators are matched"),
                 }
             };
         Ok(left)
@@ -68,7 +108,7 @@
                 left = match operator.kind {
                     TokenKind::Plus => Expr::Arithmetic(BinaryExpression::add(left, right)),
                     TokenKind::Minus => Expr::Arithmetic(BinaryExpression::sub(left, right)),
-                    token => return Err(Error::UnexpecedCharacter(token)),
+                    _ => unreachable!("only '+' and '-' are matched"),
                 }
         };
         Ok(left)
@@ -78,13 +118,17 @@
         tokens: &mut std::iter::Peekable<I>,
     ) -> Result<Expr, Error> {
         let mut left = parse_unary(tokens)?;
-        while let Some(operator) = tokens.next_if(|x| x.kind == TokenKind::Slash || x.kind == TokenKind::Star) {
+        while let Some(operator) = tokens.next_if(|x| {
+            matches!(x.kind, TokenKind::Slash | TokenKind::Star | TokenKind::TildeSlash | TokenKind::Percent)
+        }) {
 
                 let right = parse_unary(tokens)?;
                 left = match operator.kind {
                     TokenKind::Star => Expr::Arithmetic(BinaryExpression::mult(left, right)),
                     TokenKind::Slash => Expr::Arithmetic(BinaryExpression::div(left, right)),
-                    token => return Err(Error::UnexpecedCharacter(token)),
+                    TokenKind::TildeSlash => Expr::Arithmetic(BinaryExpression::int_div(left, right)),
+                    TokenKind::Percent => Expr::Arithmetic(BinaryExpression::modulo(left, right)),
+                    _ => unreachable!("only '*', '/', '~/' and '%' are matched"),
                 }
         };
         Ok(left)
@@ -96,150 +140,374 @@
         let operator = tokens.next_if(|x| x.kind == TokenKind::Minus || x.kind == TokenKind::Bang);
         match operator {
             Some(x) => {
+                // The one literal that only fits in an integer once negated.
+                if x.kind == TokenKind::Minus {
+                    let min = TokenKind::Integer(i64::MIN.unsigned_abs());
+                    if let Some(literal) = tokens.next_if(|y| y.kind == min) {
+                        let span = x.span.to(literal.span);
+                        return Ok(Expr::Literal(LiteralExpression::integer(i64::MIN).with_span(span)));
+                    }
+                }
                 let right = parse_unary(tokens)?;
+                let span = x.span.to(right.span());
                 match x.kind {
-                    TokenKind::Minus => Ok(Expr::Unary(UnaryExpression::minus(right))),
-                    TokenKind::Bang => Ok(Expr::Unary(UnaryExpression::bang(right))),
+                    TokenKind::Minus => Ok(Expr::Unary(UnaryExpression::minus(right).with_span(span))),
+                    TokenKind::Bang => Ok(Expr::Unary(UnaryExpression::bang(right).with_span(span))),
                     _ => todo!("add primary"),
                 }
             }
-            None => parse_primary(tokens),
+            None => parse_call(tokens),
+        }
+    }
+
+    pub fn parse_call<I: Iterator<Item = Token>>(
+        tokens: &mut std::iter::Peekable<I>,
+    ) -> Result<Expr, Error> {
+        let mut expr = parse_primary(tokens)?;
+        while let Some(token) = tokens.next_if(|x| x.kind == TokenKind::LeftParen || x.kind == TokenKind::Dot) {
+            expr = match token.kind {
+                TokenKind::LeftParen => finish_call(tokens, expr)?,
+                _ => {
+                    let name = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectPropertyName)?;
+                    Expr::Get(GetExpression::new(expr, name))
+                }
+            };
+        }
+        Ok(expr)
+    }
+
+    /// Parses the argument list of a call whose opening `(` has already been consumed.
+    fn finish_call<I: Iterator<Item = Token>>(
+        tokens: &mut std::iter::Peekable<I>,
+        callee: Expr,
+    ) -> Result<Expr, Error> {
+        let mut arguments = Vec::new();
+        if tokens.peek().is_some_and(|x| x.kind != TokenKind::RightParen) {
+            loop {
+                if arguments.len() >= MAX_ARGUMENTS {
+                    return Err(error_at(tokens, ErrorKind::TooManyArguments));
+                }
+                arguments.push(parse_expression(tokens)?);
+                if tokens.next_if(|x| x.kind == TokenKind::Comma).is_none() {
+                    break;
+                }
+            }
         }
+        let paren = consume(tokens, TokenKind::RightParen, ErrorKind::ExpectRightParenAfterArguments)?;
+        Ok(Expr::Call(CallExpression::new(callee, paren, arguments)))
     }
 
     pub fn parse_primary<I: Iterator<Item = Token>>(
         tokens: &mut std::iter::Peekable<I>,
     ) -> Result<Expr, Error> {
-        let token = tokens.next().ok_or(Error::ExpectExpression)?;
+        let token = match tokens.next_if(|x| x.kind != TokenKind::Eof) {
+            Some(token) => token,
+            None => return Err(error_at(tokens, ErrorKind::ExpectExpression)),
+        };
+        if token.kind == TokenKind::Identifiter {
+            return Ok(Expr::Variable(VariableExpression::new(token)));
+        }
+        if token.kind == TokenKind::Keyword(Keyword::This) {
+            return Ok(Expr::This(ThisExpression::new(token)));
+        }
+        if token.kind == TokenKind::Keyword(Keyword::Super) {
+            consume(tokens, TokenKind::Dot, ErrorKind::ExpectDotAfterSuper)?;
+            let method = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectSuperclassMethodName)?;
+            return Ok(Expr::Super(SuperExpression::new(token, method)));
+        }
         let expr = match token.kind {
-            TokenKind::Keyword(Keyword::True) => Expr::Literal(LiteralExpression::boolean(true)),
-            TokenKind::Keyword(Keyword::False) => Expr::Literal(LiteralExpression::boolean(false)),
-            TokenKind::Keyword(Keyword::Nil) => Expr::Literal(LiteralExpression::nil()),
-            TokenKind::Number(literal) => Expr::Literal(LiteralExpression::number(literal)),
-            TokenKind::String(literal) => Expr::Literal(LiteralExpression::string(literal)),
+            TokenKind::Keyword(Keyword::True) => Expr::Literal(LiteralExpression::boolean(true).with_span(token.span)),
+            TokenKind::Keyword(Keyword::False) => Expr::Literal(LiteralExpression::boolean(false).with_span(token.span)),
+            TokenKind::Keyword(Keyword::Nil) => Expr::Literal(LiteralExpression::nil().with_span(token.span)),
+            TokenKind::Number(literal) => Expr::Literal(LiteralExpression::number(literal).with_span(token.span)),
+            TokenKind::Integer(literal) => match i64::try_from(literal) {
+                Ok(literal) => Expr::Literal(LiteralExpression::integer(literal).with_span(token.span)),
+                #[cfg(feature = "bigint")]
+                Err(_) => Expr::Literal(LiteralExpression::big_integer(literal.into()).with_span(token.span)),
+                #[cfg(not(feature = "bigint"))]
+                Err(_) => return Err(ErrorKind::IntegerTooLarge.at(token.span)),
+            },
+            #[cfg(feature = "bigint")]
+            TokenKind::BigInteger(literal) => {
+                Expr::Literal(LiteralExpression::big_integer(literal).with_span(token.span))
+            }
+            TokenKind::String(literal) => Expr::Literal(LiteralExpression::string(literal).with_span(token.span)),
             TokenKind::LeftParen => {
                 let expr = parse_expression(tokens)?;
-                let _ = tokens
-                    .next_if(|x| x.kind == TokenKind::RightParen)
-                    .ok_or(Error::ExpectRightParen)?;
-                Expr::Grouping(Box::new(expr))
+                let paren = consume(tokens, TokenKind::RightParen, ErrorKind::ExpectRightParenAfterExpression)?;
+                Expr::Grouping(GroupingExpression::new(expr).with_span(token.span.to(paren.span)))
             }
-            token => return Err(Error::UnexpecedCharacter(token))
+            _ => return Err(ErrorKind::ExpectExpression.found(token)),
         };
         Ok(expr)
     }
 
 #[cfg(test)]
 mod tests {
-    use crate::expression::{Expr, BinaryExpression, LiteralExpression, UnaryExpression};
+    use crate::expression::{Error, ErrorKind, Expr, AssignExpression, BinaryExpression, CallExpression, GetExpression, GroupingExpression, LiteralExpression, LogicalExpression, SetExpression, UnaryExpression, VariableExpression};
     use crate::lexer::Lexer;
+    use crate::parser::tests::assert_same_tree;
+    use crate::span::Span;
+    use crate::token::{Token, TokenKind};
 
     use super::{
-        parse_comparison, parse_equality, parse_factor, parse_primary, parse_term, parse_unary,
+        parse_call, parse_or, parse_assignment, parse_comparison, parse_equality, parse_factor, parse_primary, parse_term, parse_unary,
     };
 
+    fn identifier(name: &str) -> Token {
+        Token::new(name.to_string(), Span::default(), TokenKind::Identifiter)
+    }
+
+    #[test]
+    fn test_parser_parse_assignment() {
+        let mut tokens = Lexer::from_iter("x = 3".chars()).peekable();
+        let expect = Expr::Assign(AssignExpression::new(
+            identifier("x"),
+            Expr::Literal(LiteralExpression::integer(3)),
+        ));
+        assert_same_tree(expect, parse_assignment(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_assignment_right_associative() {
+        let mut tokens = Lexer::from_iter("x = y = 3".chars()).peekable();
+        let expect = Expr::Assign(AssignExpression::new(
+            identifier("x"),
+            Expr::Assign(AssignExpression::new(
+                identifier("y"),
+                Expr::Literal(LiteralExpression::integer(3)),
+            )),
+        ));
+        assert_same_tree(expect, parse_assignment(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_assignment_invalid_target() {
+        let mut tokens = Lexer::from_iter("1 + x = 3".chars()).peekable();
+        assert!(matches!(parse_assignment(&mut tokens), Err(Error { kind: ErrorKind::InvalidAssignmentTarget, .. })));
+
+        let mut tokens = Lexer::from_iter("(x) = 3".chars()).peekable();
+        assert!(matches!(parse_assignment(&mut tokens), Err(Error { kind: ErrorKind::InvalidAssignmentTarget, .. })));
+    }
+
+    #[test]
+    fn test_parser_parse_logical_presidence() {
+        let mut tokens = Lexer::from_iter("a or b and c".chars()).peekable();
+        let expect = Expr::Logical(LogicalExpression::or(
+            Expr::Variable(VariableExpression::new(identifier("a"))),
+            Expr::Logical(LogicalExpression::and(
+                Expr::Variable(VariableExpression::new(identifier("b"))),
+                Expr::Variable(VariableExpression::new(identifier("c"))),
+            )),
+        ));
+        assert_same_tree(expect, parse_or(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_logical_binds_looser_than_equality() {
+        let mut tokens = Lexer::from_iter("1 == 1 and true".chars()).peekable();
+        let expect = Expr::Logical(LogicalExpression::and(
+            Expr::Equality(BinaryExpression::equal(
+                Expr::Literal(LiteralExpression::integer(1)),
+                Expr::Literal(LiteralExpression::integer(1)),
+            )),
+            Expr::Literal(LiteralExpression::boolean(true)),
+        ));
+        assert_same_tree(expect, parse_or(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_call() {
+        let mut tokens = Lexer::from_iter("f(1, x)()".chars()).peekable();
+        let paren = Token::new(")".to_string(), Span::default(), TokenKind::RightParen);
+        let expect = Expr::Call(CallExpression::new(
+            Expr::Call(CallExpression::new(
+                Expr::Variable(VariableExpression::new(identifier("f"))),
+                paren.clone(),
+                vec![
+                    Expr::Literal(LiteralExpression::integer(1)),
+                    Expr::Variable(VariableExpression::new(identifier("x"))),
+                ],
+            )),
+            paren,
+            vec![],
+        ));
+        assert_same_tree(expect, parse_call(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_call_too_many_arguments() {
+        let arguments = vec!["1"; 256].join(", ");
+        let source = format!("f({arguments})");
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        assert!(matches!(parse_call(&mut tokens), Err(Error { kind: ErrorKind::TooManyArguments, .. })));
+
+        let arguments = vec!["1"; 255].join(", ");
+        let source = format!("f({arguments})");
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        assert!(parse_call(&mut tokens).is_ok());
+    }
+
+    #[test]
+    fn test_parser_parse_property_get_and_set() {
+        let mut tokens = Lexer::from_iter("a.b.c = 1".chars()).peekable();
+        let expect = Expr::Set(SetExpression::new(
+            Expr::Get(GetExpression::new(
+                Expr::Variable(VariableExpression::new(identifier("a"))),
+                identifier("b"),
+            )),
+            identifier("c"),
+            Expr::Literal(LiteralExpression::integer(1)),
+        ));
+        assert_same_tree(expect, parse_assignment(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_property_missing_name() {
+        let mut tokens = Lexer::from_iter("a.1".chars()).peekable();
+        assert!(matches!(parse_call(&mut tokens), Err(Error { kind: ErrorKind::ExpectPropertyName, .. })));
+    }
+
+    #[test]
+    fn test_parser_parse_super() {
+        let mut tokens = Lexer::from_iter("super.cook".chars()).peekable();
+        assert!(matches!(parse_primary(&mut tokens), Ok(Expr::Super(e)) if e.method().lexeme == "cook"));
+
+        let mut tokens = Lexer::from_iter("super".chars()).peekable();
+        assert!(matches!(parse_primary(&mut tokens), Err(Error { kind: ErrorKind::ExpectDotAfterSuper, .. })));
+
+        let mut tokens = Lexer::from_iter("super.".chars()).peekable();
+        assert!(matches!(parse_primary(&mut tokens), Err(Error { kind: ErrorKind::ExpectSuperclassMethodName, .. })));
+    }
+
+    #[test]
+    fn test_parser_parse_call_unterminated() {
+        let mut tokens = Lexer::from_iter("f(1".chars()).peekable();
+        assert!(matches!(parse_call(&mut tokens), Err(Error { kind: ErrorKind::ExpectRightParenAfterArguments, .. })));
+    }
+
+    #[test]
+    fn test_parser_parse_primary_variable() {
+        let mut tokens = Lexer::from_iter("answer".chars()).peekable();
+        let expect = Expr::Variable(VariableExpression::new(identifier("answer")));
+        assert_same_tree(expect, parse_primary(&mut tokens).unwrap());
+    }
+
     #[test]
     fn test_parser_parser_equality_equal() {
         let mut tokens = Lexer::from_iter("2 == 2".chars()).peekable();
         let expect = Expr::Equality(BinaryExpression::equal(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse_equality(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_equality(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_compare_greater() {
         let mut tokens = Lexer::from_iter("2 > 2".chars()).peekable();
         let expect = Expr::Compare( BinaryExpression::greater(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse_comparison(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_comparison(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_presidence() {
         let mut tokens = Lexer::from_iter("2 + 2 * 2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::add(
-                Expr::Literal(LiteralExpression::number(2_f64)),
+                Expr::Literal(LiteralExpression::integer(2)),
             Expr::Arithmetic(BinaryExpression::mult(
-                Expr::Literal(LiteralExpression::number(2_f64)),
-                Expr::Literal(LiteralExpression::number(2_f64)),
+                Expr::Literal(LiteralExpression::integer(2)),
+                Expr::Literal(LiteralExpression::integer(2)),
             )),
         ));
-        assert_eq!(expect, parse_term(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_term(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_factor_add_negative() {
         let mut tokens = Lexer::from_iter("2 + -2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::add(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Unary(UnaryExpression::minus(Expr::Literal(LiteralExpression::number(2_f64)))),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Unary(UnaryExpression::minus(Expr::Literal(LiteralExpression::integer(2)))),
         ));
-        assert_eq!(expect, parse_term(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_term(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_term_add() {
         let mut tokens = Lexer::from_iter("2 + 2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::add(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse_term(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_term(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_term_sub() {
         let mut tokens = Lexer::from_iter("2 - 2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::sub(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse_term(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_term(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_factor_multiply_negative() {
         let mut tokens = Lexer::from_iter("2 * -2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::mult(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Unary(UnaryExpression::minus(Expr::Literal(LiteralExpression::number(2_f64)))),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Unary(UnaryExpression::minus(Expr::Literal(LiteralExpression::integer(2)))),
         ));
-        assert_eq!(expect, parse_factor(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_factor(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_factor_multiply() {
         let mut tokens = Lexer::from_iter("2 * 2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::mult(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse_factor(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_factor(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_factor_divide() {
         let mut tokens = Lexer::from_iter("2 / 2".chars()).peekable();
         let expect = Expr::Arithmetic( BinaryExpression::div(
-            Expr::Literal(LiteralExpression::number(2_f64)),
-            Expr::Literal(LiteralExpression::number(2_f64)),
+            Expr::Literal(LiteralExpression::integer(2)),
+            Expr::Literal(LiteralExpression::integer(2)),
         ));
-        assert_eq!(expect, parse_factor(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_factor(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_parse_factor_integer_division_and_modulo() {
+        let mut tokens = Lexer::from_iter("7 ~/ 2 % 3".chars()).peekable();
+        let expect = Expr::Arithmetic(BinaryExpression::modulo(
+            Expr::Arithmetic(BinaryExpression::int_div(
+                Expr::Literal(LiteralExpression::integer(7)),
+                Expr::Literal(LiteralExpression::integer(2)),
+            )),
+            Expr::Literal(LiteralExpression::integer(3)),
+        ));
+        assert_same_tree(expect, parse_factor(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_unary() {
         let mut tokens = Lexer::from_iter("-123".chars()).peekable();
-        let expect = Expr::Unary(UnaryExpression::minus(Expr::Literal(LiteralExpression::number(123_f64))));
-        assert_eq!(expect, parse_unary(&mut tokens).unwrap());
+        let expect = Expr::Unary(UnaryExpression::minus(Expr::Literal(LiteralExpression::integer(123))));
+        assert_same_tree(expect, parse_unary(&mut tokens).unwrap());
 
         let mut tokens = Lexer::from_iter("!true".chars()).peekable();
         let expect = Expr::Unary(UnaryExpression::bang(Expr::Literal(LiteralExpression::boolean(true))));
-        assert_eq!(expect, parse_unary(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_unary(&mut tokens).unwrap());
 
         let mut tokens = Lexer::from_iter("!!false".chars()).peekable();
         let expect = Expr::Unary(
@@ -247,33 +515,60 @@ mod tests {
                 UnaryExpression::bang(Expr::Literal(LiteralExpression::boolean(false))),
             )),
         );
-        assert_eq!(expect, parse_unary(&mut tokens).unwrap());
+        assert_same_tree(expect, parse_unary(&mut tokens).unwrap());
     }
 
     #[test]
     fn test_parser_parse_primary_group() {
         let mut tokens = Lexer::from_iter("( \"grouped\" )".chars()).peekable();
-        let expect = Expr::Grouping(Box::new(Expr::Literal(LiteralExpression::string("grouped".to_string()))));
-        assert_eq!(expect, parse_unary(&mut tokens).unwrap());
+        let expect = Expr::Grouping(GroupingExpression::new(Expr::Literal(LiteralExpression::string("grouped".to_string()))));
+        assert_same_tree(expect, parse_unary(&mut tokens).unwrap());
+    }
+
+    #[test]
+    fn test_parser_expression_spans() {
+        let source = "-(1 + 2) * foo.bar(x)";
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        let Expr::Arithmetic(expr) = parse_term(&mut tokens).unwrap() else {
+            panic!("Expected a multiplication");
+        };
+        let span = |expr: &Expr| &source[expr.span().start..expr.span().end];
+        assert_eq!(span(expr.left()), "-(1 + 2)");
+        assert_eq!(span(expr.right()), "foo.bar(x)");
+        assert_eq!(expr.right().span().column, 12);
+        assert_eq!(&source[expr.span().start..expr.span().end], source);
+    }
+
+    #[test]
+    fn test_parser_error_spans() {
+        let mut tokens = Lexer::from_iter("1 +\n".chars()).peekable();
+        let err = parse_term(&mut tokens).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::ExpectExpression));
+        assert_eq!((err.span.start, err.span.line, err.span.column), (3, 1, 4), "Points right after the last token");
+
+        let mut tokens = Lexer::from_iter("a + 1 = 2".chars()).peekable();
+        let err = parse_assignment(&mut tokens).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidAssignmentTarget));
+        assert_eq!((err.span.start, err.span.end), (0, 5), "Points at the whole target");
     }
 
     #[test]
     fn test_parser_parse_primary_literals() {
         let mut tokens = Lexer::from_iter("true false nil 123 \"string\"".chars()).peekable();
-        assert_eq!(
+        assert_same_tree(
             Expr::Literal(LiteralExpression::boolean(true)),
             parse_primary(&mut tokens).unwrap()
         );
-        assert_eq!(
+        assert_same_tree(
             Expr::Literal(LiteralExpression::boolean(false)),
             parse_primary(&mut tokens).unwrap()
         );
-        assert_eq!(
+        assert_same_tree(
             Expr::Literal(LiteralExpression::nil()),
             parse_primary(&mut tokens).unwrap()
         );
-        assert_eq!(Expr::Literal(LiteralExpression::number(123_f64)), parse_primary(&mut tokens).unwrap());
-        assert_eq!(
+        assert_same_tree(Expr::Literal(LiteralExpression::integer(123)), parse_primary(&mut tokens).unwrap());
+        assert_same_tree(
             Expr::Literal(LiteralExpression::string("string".to_string())),
             parse_primary(&mut tokens).unwrap()
         );
@@ -0,0 +1,691 @@
+use super::parse::parse_expression;
+use super::{consume, error_at, syncronize};
+use std::rc::Rc;
+
+use crate::expression::{Error, ErrorKind, Expr, LiteralExpression, VariableExpression, MAX_ARGUMENTS};
+use crate::statement::{
+    ClassStatement, FunctionStatement, IfStatement, JumpStatement, ReturnStatement, Stmt,
+    VarStatement, WhileStatement,
+};
+use crate::suggest;
+use crate::token::{Keyword, Token, TokenKind};
+
+pub fn parse_declaration<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+) -> Result<Stmt, Error> {
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::Class))
+        .is_some()
+    {
+        return parse_class_declaration(tokens, errors);
+    }
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::Fun))
+        .is_some()
+    {
+        return Ok(Stmt::Function(Rc::new(parse_function(tokens, errors)?)));
+    }
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::Var))
+        .is_some()
+    {
+        return parse_var_declaration(tokens);
+    }
+    parse_statement(tokens, errors)
+}
+
+/// Parses a declaration, or records why it couldn't and skips ahead to the next statement so
+/// that parsing can carry on. `in_block` says whether the declaration is inside a block, whose
+/// closing `}` recovery has to stop at.
+pub fn parse_declaration_or_recover<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+    in_block: bool,
+) -> Option<Stmt> {
+    match parse_declaration(tokens, errors) {
+        Ok(statement) => Some(statement),
+        Err(err) => {
+            errors.push(err);
+            syncronize(tokens, in_block);
+            None
+        }
+    }
+}
+
+/// Parses a class declaration after its `class` keyword.
+pub fn parse_class_declaration<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+) -> Result<Stmt, Error> {
+    let name = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectClassName)?;
+    let superclass = match tokens.next_if(|x| x.kind == TokenKind::Less) {
+        Some(_) => {
+            let superclass = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectSuperclassName)?;
+            Some(VariableExpression::new(superclass))
+        }
+        None => None,
+    };
+    consume(tokens, TokenKind::LeftBrace, ErrorKind::ExpectLeftBrace)?;
+
+    let mut methods = Vec::new();
+    loop {
+        match tokens.peek() {
+            Some(x) if x.kind == TokenKind::RightBrace => break,
+            // A broken method is skipped like a statement in a block, so that the class's `}`
+            // still ends the class.
+            Some(x) if x.kind != TokenKind::Eof => match parse_function(tokens, errors) {
+                Ok(method) => methods.push(Rc::new(method)),
+                Err(err) => {
+                    errors.push(err);
+                    syncronize(tokens, true);
+                }
+            },
+            _ => return Err(error_at(tokens, ErrorKind::ExpectRightBrace)),
+        }
+    }
+    tokens.next();
+    Ok(Stmt::Class(ClassStatement::new(name, superclass, methods)))
+}
+
+/// Parses the name, parameters and body of a function declaration or a method, after the
+/// `fun` keyword if there is one.
+pub fn parse_function<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+) -> Result<FunctionStatement, Error> {
+    let name = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectFunctionName)?;
+    consume(tokens, TokenKind::LeftParen, ErrorKind::ExpectLeftParenAfterFunctionName)?;
+
+    let mut params = Vec::new();
+    if tokens.peek().is_some_and(|x| x.kind != TokenKind::RightParen) {
+        loop {
+            if params.len() >= MAX_ARGUMENTS {
+                return Err(error_at(tokens, ErrorKind::TooManyParameters));
+            }
+            let param = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectParameterName)?;
+            params.push(param);
+            if tokens.next_if(|x| x.kind == TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+    }
+    consume(tokens, TokenKind::RightParen, ErrorKind::ExpectRightParenAfterParameters)?;
+
+    consume(tokens, TokenKind::LeftBrace, ErrorKind::ExpectLeftBrace)?;
+    let body = parse_block(tokens, errors)?;
+    Ok(FunctionStatement::new(name, params, body))
+}
+
+pub fn parse_var_declaration<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+) -> Result<Stmt, Error> {
+    let name = consume(tokens, TokenKind::Identifiter, ErrorKind::ExpectVariableName)?;
+    let initializer = match tokens.next_if(|x| x.kind == TokenKind::Equal) {
+        Some(_) => Some(parse_expression(tokens)?),
+        None => None,
+    };
+    consume_semicolon(tokens, ErrorKind::ExpectSemicolonAfterVariable)?;
+    Ok(Stmt::Var(VarStatement::new(name, initializer)))
+}
+
+pub fn parse_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+) -> Result<Stmt, Error> {
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::Print))
+        .is_some()
+    {
+        return parse_print_statement(tokens);
+    }
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::If))
+        .is_some()
+    {
+        return parse_if_statement(tokens, errors);
+    }
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::While))
+        .is_some()
+    {
+        return parse_while_statement(tokens, errors, None);
+    }
+    if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::For))
+        .is_some()
+    {
+        return parse_for_statement(tokens, errors, None);
+    }
+    if let Some(keyword) = tokens.next_if(|x| x.kind == TokenKind::Keyword(Keyword::Return)) {
+        return parse_return_statement(tokens, keyword);
+    }
+    if let Some(keyword) = tokens.next_if(|x| {
+        matches!(
+            x.kind,
+            TokenKind::Keyword(Keyword::Break) | TokenKind::Keyword(Keyword::Continue)
+        )
+    }) {
+        return parse_jump_statement(tokens, keyword);
+    }
+    if tokens
+        .next_if(|x| x.kind == TokenKind::LeftBrace)
+        .is_some()
+    {
+        return Ok(Stmt::Block(parse_block(tokens, errors)?));
+    }
+
+    let expr = parse_expression(tokens)?;
+    if let Expr::Variable(variable) = &expr {
+        if tokens.next_if(|x| x.kind == TokenKind::Colon).is_some() {
+            return parse_labeled_statement(tokens, errors, variable.name().clone());
+        }
+    }
+    consume_semicolon(tokens, ErrorKind::ExpectSemicolonAfterExpression).map_err(|mut err| {
+        err.help = misspelled_keyword(&expr).map(suggest::did_you_mean);
+        err
+    })?;
+    Ok(Stmt::Expression(expr))
+}
+
+/// The keyword an expression statement that failed to end was probably meant to start with.
+/// Only a lone identifier qualifies, as in `pritn x;`, where the statement breaks off right
+/// after it, or one called like a condition, as in `whiel (x) {}`. Errors further into the
+/// statement are ordinary syntax errors.
+fn misspelled_keyword(expr: &Expr) -> Option<&'static str> {
+    let (name, keywords) = match expr {
+        Expr::Variable(variable) => (variable.name(), Keyword::ALL.to_vec()),
+        Expr::Call(call) => match call.callee() {
+            Expr::Variable(variable) => (variable.name(), vec![Keyword::For, Keyword::If, Keyword::While]),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    suggest::closest(&name.lexeme, keywords.iter().map(Keyword::as_str))
+}
+
+/// Parses the loop following `label:`. Only loops may be labeled.
+pub fn parse_labeled_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+    label: Token,
+) -> Result<Stmt, Error> {
+    let keyword = tokens.next_if(|x| {
+        matches!(
+            x.kind,
+            TokenKind::Keyword(Keyword::While) | TokenKind::Keyword(Keyword::For)
+        )
+    });
+    match keyword.map(|x| x.kind) {
+        Some(TokenKind::Keyword(Keyword::While)) => parse_while_statement(tokens, errors, Some(label)),
+        Some(TokenKind::Keyword(Keyword::For)) => parse_for_statement(tokens, errors, Some(label)),
+        _ => Err(error_at(tokens, ErrorKind::ExpectLoopAfterLabel)),
+    }
+}
+
+pub fn parse_return_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    keyword: Token,
+) -> Result<Stmt, Error> {
+    let value = match tokens.peek() {
+        Some(x) if x.kind == TokenKind::Semicolon => None,
+        _ => Some(parse_expression(tokens)?),
+    };
+    consume_semicolon(tokens, ErrorKind::ExpectSemicolonAfterReturnValue)?;
+    Ok(Stmt::Return(ReturnStatement::new(keyword, value)))
+}
+
+/// Parses the remainder of a `break` or `continue`. Whether it actually sits inside a
+/// matching loop is checked once the whole program is parsed, see `super::loops`.
+pub fn parse_jump_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    keyword: Token,
+) -> Result<Stmt, Error> {
+    let label = tokens.next_if(|x| x.kind == TokenKind::Identifiter);
+    let error = match keyword.kind {
+        TokenKind::Keyword(Keyword::Break) => ErrorKind::ExpectSemicolonAfterBreak,
+        _ => ErrorKind::ExpectSemicolonAfterContinue,
+    };
+    consume_semicolon(tokens, error)?;
+    let stmt = JumpStatement::new(keyword, label);
+    match stmt.keyword().kind {
+        TokenKind::Keyword(Keyword::Break) => Ok(Stmt::Break(stmt)),
+        _ => Ok(Stmt::Continue(stmt)),
+    }
+}
+
+/// Parses the declarations of a block whose opening `{` has already been consumed.
+pub fn parse_block<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+) -> Result<Vec<Stmt>, Error> {
+    let mut statements = Vec::new();
+    loop {
+        match tokens.peek() {
+            Some(x) if x.kind == TokenKind::RightBrace => break,
+            Some(x) if x.kind != TokenKind::Eof => {
+                statements.extend(parse_declaration_or_recover(tokens, errors, true));
+            }
+            _ => return Err(error_at(tokens, ErrorKind::ExpectRightBrace)),
+        }
+    }
+    tokens.next();
+    Ok(statements)
+}
+
+/// Parses the remainder of an `if` statement. An `else` always binds to the nearest `if`,
+/// since the innermost call is the first to see it.
+pub fn parse_if_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+) -> Result<Stmt, Error> {
+    consume(tokens, TokenKind::LeftParen, ErrorKind::ExpectLeftParenAfterIf)?;
+    let condition = parse_expression(tokens)?;
+    consume(tokens, TokenKind::RightParen, ErrorKind::ExpectRightParenAfterCondition)?;
+
+    let then_branch = parse_statement(tokens, errors)?;
+    let else_branch = match tokens.next_if(|x| x.kind == TokenKind::Keyword(Keyword::Else)) {
+        Some(_) => Some(parse_statement(tokens, errors)?),
+        None => None,
+    };
+    Ok(Stmt::If(IfStatement::new(condition, then_branch, else_branch)))
+}
+
+pub fn parse_while_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+    label: Option<Token>,
+) -> Result<Stmt, Error> {
+    consume(tokens, TokenKind::LeftParen, ErrorKind::ExpectLeftParenAfterWhile)?;
+    let condition = parse_expression(tokens)?;
+    consume(tokens, TokenKind::RightParen, ErrorKind::ExpectRightParenAfterCondition)?;
+    let body = parse_statement(tokens, errors)?;
+    Ok(Stmt::While(WhileStatement::new(condition, body).with_label(label)))
+}
+
+/// Parses a C-style `for` loop and desugars it into an equivalent `while` loop wrapped in a
+/// block, so the interpreter only has a single looping construct. Every clause is optional and
+/// a missing condition loops forever. The increment stays attached to the `while` rather than
+/// being appended to the body, so that `continue` does not skip it.
+pub fn parse_for_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    errors: &mut Vec<Error>,
+    label: Option<Token>,
+) -> Result<Stmt, Error> {
+    consume(tokens, TokenKind::LeftParen, ErrorKind::ExpectLeftParenAfterFor)?;
+
+    let initializer = if tokens.next_if(|x| x.kind == TokenKind::Semicolon).is_some() {
+        None
+    } else if tokens
+        .next_if(|x| x.kind == TokenKind::Keyword(Keyword::Var))
+        .is_some()
+    {
+        Some(parse_var_declaration(tokens)?)
+    } else {
+        Some(parse_expression_statement(tokens)?)
+    };
+
+    let condition = match tokens.peek() {
+        Some(x) if x.kind == TokenKind::Semicolon => None,
+        _ => Some(parse_expression(tokens)?),
+    };
+    consume_semicolon(tokens, ErrorKind::ExpectSemicolonAfterLoopCondition)?;
+
+    let increment = match tokens.peek() {
+        Some(x) if x.kind == TokenKind::RightParen => None,
+        _ => Some(parse_expression(tokens)?),
+    };
+    consume(tokens, TokenKind::RightParen, ErrorKind::ExpectRightParenAfterForClauses)?;
+
+    let body = parse_statement(tokens, errors)?;
+    let condition = condition.unwrap_or(Expr::Literal(LiteralExpression::boolean(true)));
+    let mut body = Stmt::While(
+        WhileStatement::new(condition, body)
+            .with_increment(increment)
+            .with_label(label),
+    );
+    if let Some(initializer) = initializer {
+        body = Stmt::Block(vec![initializer, body]);
+    }
+    Ok(body)
+}
+
+pub fn parse_print_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+) -> Result<Stmt, Error> {
+    let expr = parse_expression(tokens)?;
+    consume_semicolon(tokens, ErrorKind::ExpectSemicolonAfterValue)?;
+    Ok(Stmt::Print(expr))
+}
+
+pub fn parse_expression_statement<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+) -> Result<Stmt, Error> {
+    let expr = parse_expression(tokens)?;
+    consume_semicolon(tokens, ErrorKind::ExpectSemicolonAfterExpression)?;
+    Ok(Stmt::Expression(expr))
+}
+
+fn consume_semicolon<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    error: ErrorKind,
+) -> Result<(), Error> {
+    consume(tokens, TokenKind::Semicolon, error).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expression::{BinaryExpression, Error, ErrorKind, Expr, LiteralExpression, VariableExpression};
+    use crate::lexer::Lexer;
+    use crate::parser::tests::assert_same_tree;
+    use crate::span::Span;
+    use std::rc::Rc;
+
+    use crate::parser::parse::parse_expression;
+    use crate::statement::{
+        ClassStatement, FunctionStatement, IfStatement, JumpStatement, ReturnStatement, Stmt,
+        VarStatement, WhileStatement,
+    };
+    use crate::token::{Keyword, Token, TokenKind};
+
+    use super::{parse_declaration, parse_statement};
+
+    #[test]
+    fn test_parser_declaration_var() {
+        let mut tokens = Lexer::from_iter("var x = 1; var y;".chars()).peekable();
+        let name = Token::new("x".to_string(), Span::default(), TokenKind::Identifiter);
+        let expect = Stmt::Var(VarStatement::new(
+            name,
+            Some(Expr::Literal(LiteralExpression::integer(1))),
+        ));
+        assert_same_tree(expect, parse_declaration(&mut tokens, &mut Vec::new()).unwrap());
+
+        let name = Token::new("y".to_string(), Span::default(), TokenKind::Identifiter);
+        let expect = Stmt::Var(VarStatement::new(name, None));
+        assert_same_tree(expect, parse_declaration(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_if_else() {
+        let mut tokens = Lexer::from_iter("if (true) print 1; else print 2;".chars()).peekable();
+        let expect = Stmt::If(IfStatement::new(
+            Expr::Literal(LiteralExpression::boolean(true)),
+            Stmt::Print(Expr::Literal(LiteralExpression::integer(1))),
+            Some(Stmt::Print(Expr::Literal(LiteralExpression::integer(2)))),
+        ));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_dangling_else() {
+        let mut tokens =
+            Lexer::from_iter("if (true) if (false) print 1; else print 2;".chars()).peekable();
+        let expect = Stmt::If(IfStatement::new(
+            Expr::Literal(LiteralExpression::boolean(true)),
+            Stmt::If(IfStatement::new(
+                Expr::Literal(LiteralExpression::boolean(false)),
+                Stmt::Print(Expr::Literal(LiteralExpression::integer(1))),
+                Some(Stmt::Print(Expr::Literal(LiteralExpression::integer(2)))),
+            )),
+            None,
+        ));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_if_missing_paren() {
+        let mut tokens = Lexer::from_iter("if true print 1;".chars()).peekable();
+        assert!(matches!(
+            parse_statement(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectLeftParenAfterIf, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_statement_while() {
+        let mut tokens = Lexer::from_iter("while (false) print 1;".chars()).peekable();
+        let expect = Stmt::While(WhileStatement::new(
+            Expr::Literal(LiteralExpression::boolean(false)),
+            Stmt::Print(Expr::Literal(LiteralExpression::integer(1))),
+        ));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_for_desugars_to_while() {
+        let source = "for (var i = 0; i < 2; i = i + 1) print i;";
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        let desugared = "{ var i = 0; while (i < 2) print i; }";
+        let mut expect = Lexer::from_iter(desugared.chars()).peekable();
+        let Stmt::Block(mut expect) = parse_statement(&mut expect, &mut Vec::new()).unwrap() else {
+            panic!("Expected a block");
+        };
+        let Some(Stmt::While(body)) = expect.pop() else {
+            panic!("Expected a while loop");
+        };
+        let increment = parse_expression(&mut Lexer::from_iter("i = i + 1".chars()).peekable());
+        expect.push(Stmt::While(body.with_increment(Some(increment.unwrap()))));
+        assert_same_tree(Stmt::Block(expect), parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_labeled_loop() {
+        let mut tokens = Lexer::from_iter("outer: while (true) break outer;".chars()).peekable();
+        let label = Token::new("outer".to_string(), Span::default(), TokenKind::Identifiter);
+        let keyword = Token::new("break".to_string(), Span::default(), TokenKind::Keyword(Keyword::Break));
+        let expect = Stmt::While(
+            WhileStatement::new(
+                Expr::Literal(LiteralExpression::boolean(true)),
+                Stmt::Break(JumpStatement::new(keyword, Some(label.clone()))),
+            )
+            .with_label(Some(label)),
+        );
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_label_requires_loop() {
+        let mut tokens = Lexer::from_iter("outer: print 1;".chars()).peekable();
+        assert!(matches!(
+            parse_statement(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectLoopAfterLabel, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_statement_continue() {
+        let mut tokens = Lexer::from_iter("continue;".chars()).peekable();
+        let keyword = Token::new("continue".to_string(), Span::default(), TokenKind::Keyword(Keyword::Continue));
+        let expect = Stmt::Continue(JumpStatement::new(keyword, None));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_for_empty_clauses() {
+        let mut tokens = Lexer::from_iter("for (;;) print 1;".chars()).peekable();
+        let expect = Stmt::While(WhileStatement::new(
+            Expr::Literal(LiteralExpression::boolean(true)),
+            Stmt::Print(Expr::Literal(LiteralExpression::integer(1))),
+        ));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_block() {
+        let mut tokens = Lexer::from_iter("{ print 1; { } }".chars()).peekable();
+        let expect = Stmt::Block(vec![
+            Stmt::Print(Expr::Literal(LiteralExpression::integer(1))),
+            Stmt::Block(vec![]),
+        ]);
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_block_unterminated() {
+        let mut tokens = Lexer::from_iter("{ print 1;".chars()).peekable();
+        assert!(matches!(
+            parse_statement(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectRightBrace, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_declaration_function() {
+        let mut tokens = Lexer::from_iter("fun add(a, b) { return a + b; }".chars()).peekable();
+        let identifier = |name: &str| Token::new(name.to_string(), Span::default(), TokenKind::Identifiter);
+        let keyword = Token::new("return".to_string(), Span::default(), TokenKind::Keyword(Keyword::Return));
+        let mut body = Lexer::from_iter("a + b".chars()).peekable();
+        let body = vec![Stmt::Return(ReturnStatement::new(
+            keyword,
+            Some(parse_expression(&mut body).unwrap()),
+        ))];
+        let expect = Stmt::Function(Rc::new(FunctionStatement::new(
+            identifier("add"),
+            vec![identifier("a"), identifier("b")],
+            body,
+        )));
+        assert_same_tree(expect, parse_declaration(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_declaration_function_errors() {
+        let mut tokens = Lexer::from_iter("fun (a) {}".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectFunctionName, .. })
+        ));
+
+        let mut tokens = Lexer::from_iter("fun f(a, 1) {}".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectParameterName, .. })
+        ));
+
+        let mut tokens = Lexer::from_iter("fun f() print 1;".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectLeftBrace, .. })
+        ));
+
+        let params: Vec<String> = (0..256).map(|i| format!("p{i}")).collect();
+        let source = format!("fun f({}) {{}}", params.join(", "));
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::TooManyParameters, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_declaration_class() {
+        let mut tokens = Lexer::from_iter("class Greeter { hello() {} bye(name) {} }".chars()).peekable();
+        let identifier = |name: &str| Token::new(name.to_string(), Span::default(), TokenKind::Identifiter);
+        let expect = Stmt::Class(ClassStatement::new(
+            identifier("Greeter"),
+            None,
+            vec![
+                Rc::new(FunctionStatement::new(identifier("hello"), vec![], vec![])),
+                Rc::new(FunctionStatement::new(identifier("bye"), vec![identifier("name")], vec![])),
+            ],
+        ));
+        assert_same_tree(expect, parse_declaration(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_declaration_class_errors() {
+        let mut tokens = Lexer::from_iter("class { }".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectClassName, .. })
+        ));
+
+        let mut tokens = Lexer::from_iter("class A { f() {}".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectRightBrace, .. })
+        ));
+
+        let mut tokens = Lexer::from_iter("class A < { }".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectSuperclassName, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_declaration_subclass() {
+        let mut tokens = Lexer::from_iter("class B < A {}".chars()).peekable();
+        let identifier = |name: &str| Token::new(name.to_string(), Span::default(), TokenKind::Identifiter);
+        let expect = Stmt::Class(ClassStatement::new(
+            identifier("B"),
+            Some(VariableExpression::new(identifier("A"))),
+            vec![],
+        ));
+        assert_same_tree(expect, parse_declaration(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_return_without_value() {
+        let mut tokens = Lexer::from_iter("return;".chars()).peekable();
+        let keyword = Token::new("return".to_string(), Span::default(), TokenKind::Keyword(Keyword::Return));
+        let expect = Stmt::Return(ReturnStatement::new(keyword, None));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_declaration_var_missing_name() {
+        let mut tokens = Lexer::from_iter("var 1 = 2;".chars()).peekable();
+        assert!(matches!(
+            parse_declaration(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectVariableName, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_statement_print() {
+        let mut tokens = Lexer::from_iter("print \"Hello\";".chars()).peekable();
+        let expect = Stmt::Print(Expr::Literal(LiteralExpression::string(
+            "Hello".to_string(),
+        )));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_expression() {
+        let mut tokens = Lexer::from_iter("1 + 2;".chars()).peekable();
+        let expect = Stmt::Expression(Expr::Arithmetic(BinaryExpression::add(
+            Expr::Literal(LiteralExpression::integer(1)),
+            Expr::Literal(LiteralExpression::integer(2)),
+        )));
+        assert_same_tree(expect, parse_statement(&mut tokens, &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn test_parser_statement_missing_semicolon() {
+        let mut tokens = Lexer::from_iter("print 1".chars()).peekable();
+        assert!(matches!(
+            parse_statement(&mut tokens, &mut Vec::new()),
+            Err(Error { kind: ErrorKind::ExpectSemicolonAfterValue, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parser_statement_suggests_misspelled_keyword() {
+        for (source, keyword) in [("pritn 1;", "print"), ("retrun x;", "return"), ("whiel (x) {}", "while")] {
+            let mut tokens = Lexer::from_iter(source.chars()).peekable();
+            let err = parse_statement(&mut tokens, &mut Vec::new()).unwrap_err();
+            assert_eq!(err.help, Some(format!("did you mean '{keyword}'?")), "{source}");
+        }
+
+        let mut tokens = Lexer::from_iter("count 1;".chars()).peekable();
+        let err = parse_statement(&mut tokens, &mut Vec::new()).unwrap_err();
+        assert_eq!(err.help, None, "Nothing looks like a keyword");
+
+        // The statement gets past the identifier, so it isn't a misspelled keyword.
+        for source in ["foo = (1 + 2;", "vars(1, 2", "vars(1, 2) print 1;", "fore.x = 1 1;"] {
+            let mut tokens = Lexer::from_iter(source.chars()).peekable();
+            let err = parse_statement(&mut tokens, &mut Vec::new()).unwrap_err();
+            assert_eq!(err.help, None, "{source}");
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+
+use crate::expression::Expr;
+use crate::statement::{FunctionStatement, Stmt};
+use crate::token::Token;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    ReadInOwnInitializer(Token),
+    AlreadyDeclared(Token),
+    TopLevelReturn(Token),
+    ReturnValueFromInitializer(Token),
+    ThisOutsideClass(Token),
+    InheritFromSelf(Token),
+    SuperOutsideClass(Token),
+    SuperWithoutSuperclass(Token),
+}
+
+impl Error {
+    pub fn token(&self) -> &Token {
+        match self {
+            Self::ReadInOwnInitializer(token) => token,
+            Self::AlreadyDeclared(token) => token,
+            Self::TopLevelReturn(token) => token,
+            Self::ReturnValueFromInitializer(token) => token,
+            Self::ThisOutsideClass(token) => token,
+            Self::InheritFromSelf(token) => token,
+            Self::SuperOutsideClass(token) => token,
+            Self::SuperWithoutSuperclass(token) => token,
+        }
+    }
+
+    /// A stable identifier for the kind of error, for tools that consume diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ReadInOwnInitializer(_) => "E0301",
+            Self::AlreadyDeclared(_) => "E0302",
+            Self::TopLevelReturn(_) => "E0303",
+            Self::ReturnValueFromInitializer(_) => "E0304",
+            Self::ThisOutsideClass(_) => "E0305",
+            Self::InheritFromSelf(_) => "E0306",
+            Self::SuperOutsideClass(_) => "E0307",
+            Self::SuperWithoutSuperclass(_) => "E0308",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::ReadInOwnInitializer(_) => "Can't read local variable in its own initializer.",
+            Self::AlreadyDeclared(_) => "Already a variable with this name in this scope.",
+            Self::TopLevelReturn(_) => "Can't return from top-level code.",
+            Self::ReturnValueFromInitializer(_) => "Can't return a value from an initializer.",
+            Self::ThisOutsideClass(_) => "Can't use 'this' outside of a class.",
+            Self::InheritFromSelf(_) => "A class can't inherit from itself.",
+            Self::SuperOutsideClass(_) => "Can't use 'super' outside of a class.",
+            Self::SuperWithoutSuperclass(_) => "Can't use 'super' in a class with no superclass.",
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let token = self.token();
+        let span = token.span;
+        write!(f, "[line {}:{}] Error at '{}': {}", span.line, span.column, token.lexeme, self.message())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionKind {
+    None,
+    Function,
+    Method,
+    Initializer,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassKind {
+    None,
+    Class,
+    Subclass,
+}
+
+/// Binds every local variable use to the scope that declares it, so that closures see the
+/// variables that were in scope where they were written rather than where they are called.
+///
+/// Globals are left unresolved and looked up dynamically by the interpreter.
+pub fn resolve(statements: &[Stmt]) -> Result<(), Error> {
+    let mut resolver = Resolver {
+        scopes: Vec::new(),
+        function: FunctionKind::None,
+        class: ClassKind::None,
+    };
+    resolver.statements(statements)
+}
+
+struct Resolver {
+    /// One map per enclosing local scope, innermost last. A variable maps to `false` while it
+    /// is declared but its initializer has not been resolved yet.
+    scopes: Vec<HashMap<String, bool>>,
+    function: FunctionKind,
+    class: ClassKind,
+}
+
+impl Resolver {
+    fn statements(&mut self, statements: &[Stmt]) -> Result<(), Error> {
+        statements.iter().try_for_each(|statement| self.statement(statement))
+    }
+
+    fn statement(&mut self, stmt: &Stmt) -> Result<(), Error> {
+        match stmt {
+            Stmt::Block(statements) => {
+                self.scopes.push(HashMap::new());
+                let result = self.statements(statements);
+                self.scopes.pop();
+                result
+            }
+            Stmt::Var(stmt) => {
+                self.declare(stmt.name())?;
+                if let Some(initializer) = stmt.initializer() {
+                    self.expression(initializer)?;
+                }
+                self.define(stmt.name());
+                Ok(())
+            }
+            Stmt::Function(stmt) => {
+                // Defined before the body is resolved so the function can call itself.
+                self.declare(stmt.name())?;
+                self.define(stmt.name());
+                self.function(stmt, FunctionKind::Function)
+            }
+            Stmt::Class(stmt) => {
+                self.declare(stmt.name())?;
+                self.define(stmt.name());
+
+                let mut kind = ClassKind::Class;
+                if let Some(superclass) = stmt.superclass() {
+                    if superclass.name().lexeme == stmt.name().lexeme {
+                        return Err(Error::InheritFromSelf(superclass.name().clone()));
+                    }
+                    kind = ClassKind::Subclass;
+                    if let Some(depth) = self.depth(superclass.name()) {
+                        superclass.resolve(depth);
+                    }
+                    // Subclass methods sit one scope further in, below a scope holding `super`.
+                    self.scopes.push(HashMap::from([("super".to_string(), true)]));
+                }
+
+                let enclosing = std::mem::replace(&mut self.class, kind);
+                // Methods are bound by nesting them in a scope that holds only `this`.
+                self.scopes.push(HashMap::from([("this".to_string(), true)]));
+                let result = stmt.methods().iter().try_for_each(|method| {
+                    let kind = match method.name().lexeme.as_str() {
+                        "init" => FunctionKind::Initializer,
+                        _ => FunctionKind::Method,
+                    };
+                    self.function(method, kind)
+                });
+                self.scopes.pop();
+                if stmt.superclass().is_some() {
+                    self.scopes.pop();
+                }
+                self.class = enclosing;
+                result
+            }
+            Stmt::Expression(expr) | Stmt::Print(expr) => self.expression(expr),
+            Stmt::If(stmt) => {
+                self.expression(stmt.condition())?;
+                self.statement(stmt.then_branch())?;
+                match stmt.else_branch() {
+                    Some(else_branch) => self.statement(else_branch),
+                    None => Ok(()),
+                }
+            }
+            Stmt::While(stmt) => {
+                self.expression(stmt.condition())?;
+                self.statement(stmt.body())?;
+                match stmt.increment() {
+                    Some(increment) => self.expression(increment),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Return(stmt) => {
+                if self.function == FunctionKind::None {
+                    return Err(Error::TopLevelReturn(stmt.keyword().clone()));
+                }
+                match stmt.value() {
+                    Some(_) if self.function == FunctionKind::Initializer => {
+                        Err(Error::ReturnValueFromInitializer(stmt.keyword().clone()))
+                    }
+                    Some(value) => self.expression(value),
+                    None => Ok(()),
+                }
+            }
+            Stmt::Break(_) | Stmt::Continue(_) => Ok(()),
+        }
+    }
+
+    fn function(&mut self, stmt: &FunctionStatement, kind: FunctionKind) -> Result<(), Error> {
+        let enclosing = std::mem::replace(&mut self.function, kind);
+        self.scopes.push(HashMap::new());
+        let result = stmt
+            .params()
+            .iter()
+            .try_for_each(|param| {
+                self.declare(param)?;
+                self.define(param);
+                Ok(())
+            })
+            .and_then(|()| self.statements(stmt.body()));
+        self.scopes.pop();
+        self.function = enclosing;
+        result
+    }
+
+    fn expression(&mut self, expr: &Expr) -> Result<(), Error> {
+        match expr {
+            Expr::Variable(e) => {
+                let name = e.name();
+                if let Some(false) = self.scopes.last().and_then(|x| x.get(&name.lexeme)) {
+                    return Err(Error::ReadInOwnInitializer(name.clone()));
+                }
+                if let Some(depth) = self.depth(name) {
+                    e.resolve(depth);
+                }
+                Ok(())
+            }
+            Expr::Assign(e) => {
+                self.expression(e.value())?;
+                if let Some(depth) = self.depth(e.name()) {
+                    e.resolve(depth);
+                }
+                Ok(())
+            }
+            Expr::Arithmetic(e) | Expr::Compare(e) | Expr::Equality(e) => {
+                self.expression(e.left())?;
+                self.expression(e.right())
+            }
+            Expr::Logical(e) => {
+                self.expression(e.left())?;
+                self.expression(e.right())
+            }
+            Expr::Call(e) => {
+                self.expression(e.callee())?;
+                e.arguments()
+                    .iter()
+                    .try_for_each(|argument| self.expression(argument))
+            }
+            Expr::Get(e) => self.expression(e.object()),
+            Expr::Set(e) => {
+                self.expression(e.value())?;
+                self.expression(e.object())
+            }
+            Expr::This(e) => {
+                if self.class == ClassKind::None {
+                    return Err(Error::ThisOutsideClass(e.keyword().clone()));
+                }
+                if let Some(depth) = self.depth(e.keyword()) {
+                    e.resolve(depth);
+                }
+                Ok(())
+            }
+            Expr::Super(e) => {
+                match self.class {
+                    ClassKind::None => return Err(Error::SuperOutsideClass(e.keyword().clone())),
+                    ClassKind::Class => return Err(Error::SuperWithoutSuperclass(e.keyword().clone())),
+                    ClassKind::Subclass => {}
+                }
+                if let Some(depth) = self.depth(e.keyword()) {
+                    e.resolve(depth);
+                }
+                Ok(())
+            }
+            Expr::Grouping(e) => self.expression(e.expression()),
+            Expr::Unary(e) => self.expression(e.right()),
+            Expr::Literal(_) => Ok(()),
+        }
+    }
+
+    fn declare(&mut self, name: &Token) -> Result<(), Error> {
+        let Some(scope) = self.scopes.last_mut() else {
+            return Ok(());
+        };
+        if scope.contains_key(&name.lexeme) {
+            return Err(Error::AlreadyDeclared(name.clone()));
+        }
+        scope.insert(name.lexeme.clone(), false);
+        Ok(())
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), true);
+        }
+    }
+
+    /// The number of scopes between the innermost one and the one declaring `name`.
+    fn depth(&self, name: &Token) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.contains_key(&name.lexeme))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve, Error};
+    use crate::expression::Expr;
+    use crate::lexer::Lexer;
+    use crate::parser::parse;
+    use crate::statement::Stmt;
+
+    fn check(source: &str) -> Result<Vec<Stmt>, Error> {
+        let mut tokens = Lexer::from_iter(source.chars()).peekable();
+        let statements = parse(&mut tokens).unwrap();
+        resolve(&statements)?;
+        Ok(statements)
+    }
+
+    #[test]
+    fn resolve_depths() {
+        let statements = check("var g; { var a; { a; g; } }").unwrap();
+        let Stmt::Block(outer) = &statements[1] else {
+            panic!("Expected a block");
+        };
+        let Stmt::Block(inner) = &outer[1] else {
+            panic!("Expected a block");
+        };
+        let depths: Vec<Option<usize>> = inner
+            .iter()
+            .map(|statement| match statement {
+                Stmt::Expression(Expr::Variable(e)) => e.depth(),
+                _ => panic!("Expected a variable expression"),
+            })
+            .collect();
+        assert_eq!(depths, vec![Some(1), None]);
+    }
+
+    #[test]
+    fn resolve_read_in_own_initializer() {
+        let err = check("var a = 1; { var a = a; }").unwrap_err();
+        assert!(matches!(&err, Error::ReadInOwnInitializer(token) if token.lexeme == "a"));
+        assert_eq!(
+            err.to_string(),
+            "[line 1:22] Error at 'a': Can't read local variable in its own initializer."
+        );
+        assert!(check("var a = 1; var a = a;").is_ok(), "Globals may refer to themselves");
+    }
+
+    #[test]
+    fn resolve_already_declared() {
+        let err = check("{\n  var a;\n  var a;\n}").unwrap_err();
+        assert!(matches!(&err, Error::AlreadyDeclared(token) if token.span.line == 3));
+        assert!(check("fun f(a, a) {}").is_err());
+        assert!(check("var a; var a;").is_ok(), "Globals may be redeclared");
+        assert!(check("{ var a; { var a; } }").is_ok(), "Shadowing is allowed");
+    }
+
+    #[test]
+    fn resolve_top_level_return() {
+        let err = check("print 1;\nreturn 2;").unwrap_err();
+        assert!(matches!(&err, Error::TopLevelReturn(token) if token.span.line == 2));
+        assert!(check("{ return; }").is_err());
+        assert!(check("fun f() { { return 1; } }").is_ok());
+    }
+
+    #[test]
+    fn resolve_return_value_from_initializer() {
+        let err = check("class A { init() { return 1; } }").unwrap_err();
+        assert!(matches!(&err, Error::ReturnValueFromInitializer(token) if token.lexeme == "return"));
+        assert!(check("class A { init() { return; } }").is_ok());
+        assert!(check("class A { other() { return 1; } }").is_ok());
+    }
+
+    #[test]
+    fn resolve_this_outside_class() {
+        let err = check("print this;").unwrap_err();
+        assert_eq!(err.to_string(), "[line 1:7] Error at 'this': Can't use 'this' outside of a class.");
+        assert!(check("fun f() { return this; }").is_err());
+        assert!(check("class A { f() { fun g() { return this; } } }").is_ok());
+    }
+
+    #[test]
+    fn resolve_inherit_from_self() {
+        let err = check("class A < A {}").unwrap_err();
+        assert_eq!(err.to_string(), "[line 1:11] Error at 'A': A class can't inherit from itself.");
+    }
+
+    #[test]
+    fn resolve_super_misuse() {
+        let err = check("super.f();").unwrap_err();
+        assert!(matches!(&err, Error::SuperOutsideClass(token) if token.lexeme == "super"));
+
+        let err = check("class A {\n  f() { super.f(); }\n}").unwrap_err();
+        assert!(matches!(&err, Error::SuperWithoutSuperclass(token) if token.span.line == 2));
+
+        assert!(check("class A {} class B < A { f() { super.f(); } }").is_ok());
+    }
+}
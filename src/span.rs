@@ -0,0 +1,47 @@
+/// A region of source text: the byte offsets it covers plus the 1-based line and column it
+/// starts at.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize, line: u32, column: u32) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+
+    /// The smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Self {
+        let (first, last) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Self {
+            end: first.end.max(last.end),
+            ..first
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Span;
+
+    #[test]
+    fn test_span_to_covers_both() {
+        let a = Span::new(4, 6, 1, 5);
+        let b = Span::new(10, 13, 2, 3);
+        for span in [a.to(b), b.to(a)] {
+            assert_eq!((span.start, span.end, span.line, span.column), (4, 13, 1, 5));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::rc::Rc;
+
+use super::FunctionStatement;
+use crate::expression::VariableExpression;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassStatement {
+    name: Token,
+    superclass: Option<VariableExpression>,
+    methods: Vec<Rc<FunctionStatement>>,
+}
+
+impl std::fmt::Display for ClassStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "class {}", self.name.lexeme)?;
+        if let Some(superclass) = &self.superclass {
+            write!(f, " < {superclass}")?;
+        }
+        write!(f, " {{")?;
+        for method in &self.methods {
+            write!(f, " {method}")?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl ClassStatement {
+    pub fn new(
+        name: Token,
+        superclass: Option<VariableExpression>,
+        methods: Vec<Rc<FunctionStatement>>,
+    ) -> Self {
+        Self {
+            name,
+            superclass,
+            methods,
+        }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn superclass(&self) -> Option<&VariableExpression> {
+        self.superclass.as_ref()
+    }
+
+    pub fn methods(&self) -> &[Rc<FunctionStatement>] {
+        &self.methods
+    }
+}
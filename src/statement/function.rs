@@ -0,0 +1,38 @@
+use super::Stmt;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FunctionStatement {
+    name: Token,
+    params: Vec<Token>,
+    body: Vec<Stmt>,
+}
+
+impl std::fmt::Display for FunctionStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<&str> = self.params.iter().map(|x| x.lexeme.as_str()).collect();
+        write!(f, "fun {}({}) {{", self.name.lexeme, params.join(", "))?;
+        for statement in &self.body {
+            write!(f, " {statement}")?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl FunctionStatement {
+    pub fn new(name: Token, params: Vec<Token>, body: Vec<Stmt>) -> Self {
+        Self { name, params, body }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn params(&self) -> &[Token] {
+        &self.params
+    }
+
+    pub fn body(&self) -> &[Stmt] {
+        &self.body
+    }
+}
@@ -0,0 +1,41 @@
+use super::Stmt;
+use crate::expression::Expr;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct IfStatement {
+    condition: Expr,
+    then_branch: Box<Stmt>,
+    else_branch: Option<Box<Stmt>>,
+}
+
+impl std::fmt::Display for IfStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "if ({}) {}", self.condition, self.then_branch)?;
+        if let Some(else_branch) = &self.else_branch {
+            write!(f, " else {else_branch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl IfStatement {
+    pub fn new(condition: Expr, then_branch: Stmt, else_branch: Option<Stmt>) -> Self {
+        Self {
+            condition,
+            then_branch: Box::new(then_branch),
+            else_branch: else_branch.map(Box::new),
+        }
+    }
+
+    pub fn condition(&self) -> &Expr {
+        &self.condition
+    }
+
+    pub fn then_branch(&self) -> &Stmt {
+        self.then_branch.as_ref()
+    }
+
+    pub fn else_branch(&self) -> Option<&Stmt> {
+        self.else_branch.as_deref()
+    }
+}
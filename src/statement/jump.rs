@@ -0,0 +1,31 @@
+use crate::token::Token;
+
+/// A `break` or `continue`, optionally naming the labeled loop it jumps out of.
+#[derive(Debug, PartialEq, Clone)]
+pub struct JumpStatement {
+    keyword: Token,
+    label: Option<Token>,
+}
+
+impl std::fmt::Display for JumpStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "{} {};", self.keyword.lexeme, label.lexeme),
+            None => write!(f, "{};", self.keyword.lexeme),
+        }
+    }
+}
+
+impl JumpStatement {
+    pub fn new(keyword: Token, label: Option<Token>) -> Self {
+        Self { keyword, label }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn label(&self) -> Option<&Token> {
+        self.label.as_ref()
+    }
+}
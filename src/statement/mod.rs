@@ -0,0 +1,59 @@
+mod class;
+mod function;
+mod if_else;
+mod jump;
+mod return_statement;
+mod var;
+mod while_loop;
+
+pub use self::class::ClassStatement;
+pub use self::function::FunctionStatement;
+pub use self::if_else::IfStatement;
+pub use self::jump::JumpStatement;
+pub use self::return_statement::ReturnStatement;
+pub use self::var::VarStatement;
+pub use self::while_loop::WhileStatement;
+
+use std::rc::Rc;
+
+use crate::expression::Expr;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Stmt {
+    Block(Vec<Stmt>),
+    Break(jump::JumpStatement),
+    Class(class::ClassStatement),
+    Continue(jump::JumpStatement),
+    Expression(Expr),
+    /// Shared so that every runtime function created from the declaration reuses its body.
+    Function(Rc<function::FunctionStatement>),
+    If(if_else::IfStatement),
+    Print(Expr),
+    Return(return_statement::ReturnStatement),
+    Var(var::VarStatement),
+    While(while_loop::WhileStatement),
+}
+
+impl std::fmt::Display for Stmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Block(statements) => {
+                write!(f, "{{")?;
+                for statement in statements {
+                    write!(f, " {statement}")?;
+                }
+                write!(f, " }}")
+            }
+            Self::Break(stmt) => write!(f, "{stmt}"),
+            Self::Class(stmt) => write!(f, "{stmt}"),
+            Self::Continue(stmt) => write!(f, "{stmt}"),
+            Self::Expression(expr) => write!(f, "{expr};"),
+            Self::Function(stmt) => write!(f, "{stmt}"),
+            Self::If(stmt) => write!(f, "{stmt}"),
+            Self::Print(expr) => write!(f, "print {expr};"),
+            Self::Return(stmt) => write!(f, "{stmt}"),
+            Self::Var(stmt) => write!(f, "{stmt}"),
+            Self::While(stmt) => write!(f, "{stmt}"),
+        }
+    }
+}
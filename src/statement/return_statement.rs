@@ -0,0 +1,31 @@
+use crate::expression::Expr;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ReturnStatement {
+    keyword: Token,
+    value: Option<Expr>,
+}
+
+impl std::fmt::Display for ReturnStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "return {value};"),
+            None => write!(f, "return;"),
+        }
+    }
+}
+
+impl ReturnStatement {
+    pub fn new(keyword: Token, value: Option<Expr>) -> Self {
+        Self { keyword, value }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn value(&self) -> Option<&Expr> {
+        self.value.as_ref()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::expression::Expr;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VarStatement {
+    name: Token,
+    initializer: Option<Expr>,
+}
+
+impl std::fmt::Display for VarStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.initializer {
+            Some(initializer) => write!(f, "var {} = {initializer};", self.name.lexeme),
+            None => write!(f, "var {};", self.name.lexeme),
+        }
+    }
+}
+
+impl VarStatement {
+    pub fn new(name: Token, initializer: Option<Expr>) -> Self {
+        Self { name, initializer }
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn initializer(&self) -> Option<&Expr> {
+        self.initializer.as_ref()
+    }
+}
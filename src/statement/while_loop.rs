@@ -0,0 +1,71 @@
+use super::Stmt;
+use crate::expression::Expr;
+use crate::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct WhileStatement {
+    label: Option<Token>,
+    condition: Expr,
+    body: Box<Stmt>,
+    increment: Option<Expr>,
+}
+
+impl std::fmt::Display for WhileStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(label) = &self.label {
+            write!(f, "{}: ", label.lexeme)?;
+        }
+        match &self.increment {
+            Some(increment) => write!(f, "while ({}; {increment}) {}", self.condition, self.body),
+            None => write!(f, "while ({}) {}", self.condition, self.body),
+        }
+    }
+}
+
+impl WhileStatement {
+    pub fn new(condition: Expr, body: Stmt) -> Self {
+        Self {
+            label: None,
+            condition,
+            body: Box::new(body),
+            increment: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: Option<Token>) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// The increment of a desugared `for` loop. It runs after every iteration of the body,
+    /// including ones cut short by `continue`.
+    pub fn with_increment(mut self, increment: Option<Expr>) -> Self {
+        self.increment = increment;
+        self
+    }
+
+    pub fn label(&self) -> Option<&Token> {
+        self.label.as_ref()
+    }
+
+    pub fn condition(&self) -> &Expr {
+        &self.condition
+    }
+
+    pub fn body(&self) -> &Stmt {
+        self.body.as_ref()
+    }
+
+    pub fn increment(&self) -> Option<&Expr> {
+        self.increment.as_ref()
+    }
+
+    /// Whether a `break` or `continue` carrying `label` targets this loop. Unlabeled jumps
+    /// always target the innermost loop.
+    pub fn is_target(&self, label: Option<&str>) -> bool {
+        match label {
+            Some(label) => self.label.as_ref().is_some_and(|x| x.lexeme == label),
+            None => true,
+        }
+    }
+}
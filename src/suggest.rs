@@ -0,0 +1,74 @@
+/// The number of single-character insertions, deletions, substitutions and swaps of adjacent
+/// characters needed to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Three rows of the usual dynamic programming table: two back, one back and the current one.
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Picks the candidate `name` was most likely a typo of, if any is close enough: within a third
+/// of the name's length, and always allowing a single mistake unless that would change the
+/// whole name.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let length = name.chars().count();
+    let limit = (length / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|x| *x != name)
+        .map(|x| (edit_distance(name, x), x))
+        .filter(|(distance, _)| *distance <= limit && *distance < length)
+        // Ties go to the alphabetically first candidate, so that suggestions don't depend on
+        // hash map order.
+        .min()
+        .map(|(_, x)| x)
+}
+
+/// The hint shown alongside an error when `suggestion` looks like what was meant.
+pub fn did_you_mean(suggestion: &str) -> String {
+    format!("did you mean '{suggestion}'?")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest, edit_distance};
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("count", "cont"), 1);
+        assert_eq!(edit_distance("pritn", "print"), 1, "A swap counts once");
+        assert_eq!(edit_distance("retrun", "return"), 1);
+    }
+
+    #[test]
+    fn test_closest() {
+        let names = ["count", "counter", "total"];
+        assert_eq!(closest("cont", names), Some("count"));
+        assert_eq!(closest("countre", names), Some("counter"));
+        assert_eq!(closest("x", names), None);
+        assert_eq!(closest("a", ["b"]), None, "Single letters are never typos of each other");
+        assert_eq!(closest("count", names), None, "The name itself is never suggested");
+        assert_eq!(closest("ab", ["ac", "aa"]), Some("aa"), "Ties are broken alphabetically");
+    }
+}
@@ -1,13 +1,17 @@
-#[derive(Debug, PartialEq)]
+use crate::span::Span;
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum TokenKind {
     // Single-character tokens.
     LeftParen,
     RightParen,
     LeftBrace,
     RightBrace,
+    Colon,
     Comma,
     Dot,
     Minus,
+    Percent,
     Plus,
     Semicolon,
     Slash,
@@ -22,21 +26,33 @@ pub enum TokenKind {
     GreatherEqual,
     Less,
     LessEqual,
+    TildeSlash,
 
     // Literals
     Identifiter,
     String(String),
     Number(f64),
+    /// Unsigned, since the minus sign is a token of its own. Whether the literal fits in an
+    /// integer is up to the parser, which knows when it is negated.
+    Integer(u64),
+    /// An integer literal too large for a `u64`.
+    #[cfg(feature = "bigint")]
+    BigInteger(num_bigint::BigInt),
 
     Keyword(Keyword),
 
     Error(Error),
+
+    /// Marks the end of the source, so that errors there still have a position.
+    Eof,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum Keyword {
     And,
+    Break,
     Class,
+    Continue,
     Else,
     False,
     Fun,
@@ -53,21 +69,117 @@ pub enum Keyword {
     While,
 }
 
-#[derive(PartialEq, Debug)]
+impl Keyword {
+    pub const ALL: [Keyword; 18] = [
+        Keyword::And,
+        Keyword::Break,
+        Keyword::Class,
+        Keyword::Continue,
+        Keyword::Else,
+        Keyword::False,
+        Keyword::Fun,
+        Keyword::For,
+        Keyword::If,
+        Keyword::Nil,
+        Keyword::Or,
+        Keyword::Print,
+        Keyword::Return,
+        Keyword::Super,
+        Keyword::This,
+        Keyword::True,
+        Keyword::Var,
+        Keyword::While,
+    ];
+
+    /// The keyword as written in source.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::And => "and",
+            Self::Break => "break",
+            Self::Class => "class",
+            Self::Continue => "continue",
+            Self::Else => "else",
+            Self::False => "false",
+            Self::Fun => "fun",
+            Self::For => "for",
+            Self::If => "if",
+            Self::Nil => "nil",
+            Self::Or => "or",
+            Self::Print => "print",
+            Self::Return => "return",
+            Self::Super => "super",
+            Self::This => "this",
+            Self::True => "true",
+            Self::Var => "var",
+            Self::While => "while",
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub enum Error {
     UnexpectedCharacter,
     UnterminatedString,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Token {
     pub kind: TokenKind,
     pub lexeme: String,
-    pub line: u32,
+    pub span: Span,
 }
 
 impl Token {
-    pub fn new(lexeme: String, line: u32, kind: TokenKind) -> Self {
-        Self { kind, lexeme, line }
+    pub fn new(lexeme: String, span: Span, kind: TokenKind) -> Self {
+        Self { kind, lexeme, span }
+    }
+}
+
+/// Describes the kind of token, as in "expected ';'" or "expected identifier".
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Self::LeftParen => "(",
+            Self::RightParen => ")",
+            Self::LeftBrace => "{",
+            Self::RightBrace => "}",
+            Self::Colon => ":",
+            Self::Comma => ",",
+            Self::Dot => ".",
+            Self::Minus => "-",
+            Self::Percent => "%",
+            Self::Plus => "+",
+            Self::Semicolon => ";",
+            Self::Slash => "/",
+            Self::Star => "*",
+            Self::Bang => "!",
+            Self::BangEqual => "!=",
+            Self::Equal => "=",
+            Self::EqualEqual => "==",
+            Self::Greather => ">",
+            Self::GreatherEqual => ">=",
+            Self::Less => "<",
+            Self::LessEqual => "<=",
+            Self::TildeSlash => "~/",
+            Self::Keyword(keyword) => keyword.as_str(),
+            Self::Identifiter => return write!(f, "identifier"),
+            Self::String(_) => return write!(f, "string"),
+            Self::Number(_) | Self::Integer(_) => return write!(f, "number"),
+            #[cfg(feature = "bigint")]
+            Self::BigInteger(_) => return write!(f, "number"),
+            Self::Error(_) => return write!(f, "invalid token"),
+            Self::Eof => return write!(f, "end of file"),
+        };
+        write!(f, "'{symbol}'")
+    }
+}
+
+/// Describes the token as it was written, as in "found 'print'".
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            TokenKind::Eof => write!(f, "end of file"),
+            _ => write!(f, "'{}'", self.lexeme),
+        }
     }
 }